@@ -71,11 +71,13 @@ impl BasicPlot {
 }
 
 pub fn main() {
-    basic_plot().unwrap();
+    for warning in basic_plot().unwrap() {
+        eprintln!("{}", warning);
+    }
 }
 
 /// Produces a basic plot by instantiating and configuring the structure, before adding data to it.
-pub fn basic_plot() -> RenderResult<()> {
+pub fn basic_plot() -> RenderResult<Vec<GnuplotDiagnostic>> {
 
     // initialize the plot
     let mut plot = BasicPlot::new();
@@ -111,6 +113,10 @@ mod tests {
 
     #[test]
     fn test_basic_plot(){
+        let backend = std::sync::Arc::new(RecordingBackend::new());
+        set_default_backend(backend.clone());
+
         assert!(basic_plot().is_ok());
+        assert_eq!(backend.recordings().len(), 1);
     }
 }
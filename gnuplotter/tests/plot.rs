@@ -0,0 +1,68 @@
+use gnuplotter::maybe::Maybe;
+use gnuplotter::prelude::{Axis, Config, Label, Plot, Required, Series, Title, X, Y};
+
+#[derive(Clone, PartialEq, Eq, Debug, Default, Axis)]
+pub struct XAxis
+{
+    label: Required<Label<X>>
+}
+
+#[derive(Clone, PartialEq, Eq, Debug, Default, Axis)]
+pub struct YAxis
+{
+    label: Maybe<Label<Y>>
+}
+
+#[derive(Clone, PartialEq, Debug, Default, Plot)]
+pub struct BasicPlot {
+    config: Config,
+    title: Maybe<Title>,
+    x: XAxis,
+    y: YAxis,
+    series: Series<f64>,
+}
+
+/// A plot with every required value, but without an output file
+fn plot_without_output() -> BasicPlot {
+    let mut plot = BasicPlot::default();
+    plot.x().label().update("X");
+    let mut serie = Serie::with_title("A");
+    serie.add(1.0);
+    serie.add(4.0);
+    plot.series().add(serie);
+
+    plot
+}
+
+#[test]
+fn test_rendering_a_plot_without_output_fails() {
+    let backend = RecordingBackend::new();
+
+    let result = plot_without_output().render_with(&backend);
+
+    assert!(matches!(result, Err(RenderError::CommandGenerationFailed(_))));
+    assert!(backend.recordings().is_empty());
+}
+
+#[test]
+fn test_a_missing_required_value_fails_the_plot() {
+    let mut plot = plot_without_output();
+    plot.config().terminal().output().update("./plot.png");
+    *plot.x().label() = Required::Missing;
+
+    assert!(matches!(plot.render_with(&RecordingBackend::new()), Err(RenderError::CommandGenerationFailed(_))));
+}
+
+#[test]
+fn test_a_plot_without_output_is_rendered_into_memory_and_checked() {
+    let backend = RecordingBackend::new();
+    let plot = plot_without_output();
+
+    plot.render_to_memory_with(&Svg::default(), &backend).unwrap();
+    plot.check_with(&backend).unwrap();
+
+    let recordings = backend.recordings();
+    assert_eq!(recordings.len(), 2);
+    assert!(recordings[0].commands.iter().any(|command| command.to_string().starts_with("set term svg")));
+    assert!(recordings[1].commands.iter().all(|command| !command.to_string().starts_with("set term png")));
+}
@@ -0,0 +1,103 @@
+use gnuplotter::prelude::{Config, Plot, Series};
+
+/// A terminal gnuplotter does not ship with, defined the way a downstream crate would
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Emf {
+    output: Output
+}
+
+impl Emf {
+    pub fn output(&mut self) -> &mut Output {
+        &mut self.output
+    }
+}
+
+impl GnuCommandFactory for Emf {
+    fn as_commands(&self) -> GnuCommandFactoryResult {
+        terminal_as_commands(self, "Emf")
+    }
+}
+
+impl Terminal for Emf {
+    fn name(&self) -> &'static str {
+        "emf"
+    }
+
+    fn extension(&self) -> &'static str {
+        "emf"
+    }
+
+    fn mime_type(&self) -> &'static str {
+        "image/emf"
+    }
+
+    fn capabilities(&self) -> TerminalCapabilities {
+        TerminalCapabilities {
+            vector: true,
+            ..Default::default()
+        }
+    }
+
+    fn terminal_output(&self) -> &Output {
+        &self.output
+    }
+
+    fn terminal_output_mut(&mut self) -> &mut Output {
+        &mut self.output
+    }
+
+    fn term_commands(&self) -> GnuCommandFactoryResult {
+        Ok(vec![GnuCommand::new("set term emf enhanced").requires(Version::new(4, 0, 0))].into())
+    }
+}
+
+#[derive(Clone, PartialEq, Debug, Default, Plot)]
+pub struct EmfPlot {
+    config: Config<Emf>,
+    series: Series<f64>,
+}
+
+#[test]
+fn test_a_custom_terminal_selects_its_output_and_terminal() {
+    let mut config = Config::new(Emf::default());
+    config.terminal().output().update("./plot.emf");
+
+    assert_eq!(config.as_commands().ok().unwrap(), vec![
+        GnuCommand::new("set output './plot.emf'"),
+        GnuCommand::new("set term emf enhanced").requires(Version::new(4, 0, 0))
+    ]);
+}
+
+#[test]
+fn test_a_custom_terminal_requires_an_output() {
+    let config = Config::new(Emf::default());
+
+    assert!(config.as_commands().is_err());
+}
+
+#[test]
+fn test_a_custom_terminal_is_chosen_at_runtime() {
+    let terminals: Vec<Box<dyn Terminal>> = vec![Box::new(Svg::default()), Box::new(Emf::default())];
+    for terminal in terminals {
+        let mut config = Config::new(terminal);
+        config.terminal().update_output("./some-file");
+
+        assert_eq!(config.terminal().output_filename(), Some("./some-file".into()));
+        assert!(config.clone().as_commands().is_ok());
+    }
+}
+
+#[test]
+fn test_a_plot_is_rendered_using_a_custom_terminal() {
+    let backend = RecordingBackend::new();
+    let mut plot = EmfPlot::default();
+    plot.config().terminal().output().update("./plot.emf");
+    let mut serie = Serie::with_title("A");
+    serie.add(1.0);
+    plot.series().add(serie);
+
+    plot.render_with(&backend).unwrap();
+
+    let commands = backend.last().unwrap().commands;
+    assert_eq!(commands[1], GnuCommand::new("set term emf enhanced").requires(Version::new(4, 0, 0)));
+}
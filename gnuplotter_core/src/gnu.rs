@@ -1,4 +1,5 @@
 pub mod command;
 pub mod dimension;
 pub mod data;
-pub mod render;
\ No newline at end of file
+pub mod render;
+pub mod script;
\ No newline at end of file
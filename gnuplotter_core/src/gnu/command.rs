@@ -1,10 +1,13 @@
 use std::collections::VecDeque;
 use std::fmt::{Debug, Display, Formatter, Write};
+use std::sync::Arc;
 use crate::prelude::*;
 
 pub mod title;
 pub mod axis;
 pub mod config;
+pub mod version;
+mod text;
 
 pub mod prelude {
     pub use super::title::*;
@@ -12,13 +15,61 @@ pub mod prelude {
     pub use super::axis::label::*;
     pub use super::config::*;
     pub use super::config::terminal::*;
+    pub use super::config::terminal::svg::*;
+    pub use super::config::terminal::pdf_cairo::*;
+    pub use super::config::terminal::dumb::*;
+    pub use super::config::terminal::post_script::*;
+    pub use super::config::terminal::latex::*;
+    pub use super::config::terminal::canvas::*;
+    pub use super::config::terminal::gif_animate::*;
+    pub use super::config::terminal::sixel::*;
     pub use super::config::filename::*;
+    pub use super::version::*;
 
     pub use super::*;
 }
 
+/// A single gnuplot command, optionally along with the oldest gnuplot version supporting it. It
+/// holds on to the data files it reads, so they are only removed once the command is dropped after
+/// rendering.
+#[derive(Debug, Clone)]
+pub struct GnuCommand(String, Option<Version>, Option<Box<Details>>);
+
+/// What a command carries besides its text and version. Most commands carry none of it, so it is
+/// only allocated once needed.
+#[derive(Debug, Clone, Default)]
+struct Details {
+    files: Vec<Arc<DataFile>>,
+    data: Vec<DataReference>,
+    typesetting: Option<Typesetting>
+}
+
+/// A data file read by a command, which names it in a quoted string
 #[derive(Debug, Clone, PartialEq)]
-pub struct GnuCommand(String);
+pub(crate) struct DataReference {
+    pub(crate) path: String,
+    /// The file holds binary records instead of text, so it can't be inlined as a datablock
+    pub(crate) binary: bool
+}
+
+/// How the text of a command depends on the terminal rendering it. Commands without it are
+/// written the same way for every terminal.
+#[derive(Debug, Clone)]
+enum Typesetting {
+    /// The command selects a terminal typesetting text using LaTeX
+    LatexTerminal,
+    /// The command is written differently for terminals typesetting text using LaTeX
+    Latex {
+        plain: String,
+        latex: String
+    }
+}
+
+impl PartialEq for GnuCommand {
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0 && self.1 == other.1
+    }
+}
 
 impl Display for GnuCommand {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
@@ -33,11 +84,112 @@ impl From<GnuCommand> for String {
 }
 
 impl GnuCommand {
-    pub(crate) fn new<T>(command: T) -> Self
+    /// A command written to gnuplot as is, e.g. by a `Terminal` gnuplotter does not ship with
+    pub fn new<T>(command: T) -> Self
     where
         String: From<T>
     {
-        GnuCommand(command.into())
+        GnuCommand(command.into(), None, None)
+    }
+
+    /// Marks the command as requiring at least the given version of gnuplot
+    pub fn requires(mut self, version: Version) -> Self {
+        self.1 = Some(version);
+        self
+    }
+
+    /// Keeps the data file alive for as long as the command
+    pub(crate) fn reading(mut self, file: Arc<DataFile>) -> Self {
+        self.details().files.push(file);
+        self
+    }
+
+    /// Marks the command as reading data from the file, so the file can be inlined or exported
+    /// along with the command
+    pub(crate) fn reads(mut self, path: &str) -> Self {
+        self.details().data.push(DataReference { path: path.into(), binary: false });
+        self
+    }
+
+    /// Marks the command as reading binary records from the file, which can be exported along
+    /// with the command but not inlined
+    pub(crate) fn reads_binary(mut self, path: &str) -> Self {
+        self.details().data.push(DataReference { path: path.into(), binary: true });
+        self
+    }
+
+    /// The data files read by the command
+    pub(crate) fn data(&self) -> &[DataReference] {
+        self.2.as_ref().map(|details| details.data.as_slice()).unwrap_or_default()
+    }
+
+    /// Replaces the command by another one when the plot is rendered by a terminal typesetting
+    /// text using LaTeX
+    pub(crate) fn with_latex<T>(mut self, command: T) -> Self
+    where
+        String: From<T>
+    {
+        let plain = self.0.clone();
+        self.details().typesetting = Some(Typesetting::Latex { plain, latex: command.into() });
+        self
+    }
+
+    /// Marks the command as selecting a terminal typesetting text using LaTeX
+    pub(crate) fn typesetting_latex(mut self) -> Self {
+        self.details().typesetting = Some(Typesetting::LatexTerminal);
+        self
+    }
+
+    pub fn required_version(&self) -> Option<Version> {
+        self.1
+    }
+
+    /// Writes each command as the terminal rendering it expects. That is the terminal selected
+    /// last before the command, or the first one selected after it for commands preceding all
+    /// terminals, e.g. a title set before the terminal.
+    pub fn typeset(commands: VecDeque<GnuCommand>) -> VecDeque<GnuCommand> {
+        let mut latex = commands
+            .iter()
+            .find(|command| command.selects_term())
+            .is_some_and(GnuCommand::selects_latex_term);
+
+        commands
+            .into_iter()
+            .map(|mut command| {
+                if command.selects_term() {
+                    latex = command.selects_latex_term();
+                }
+                if let Some(Typesetting::Latex { plain, latex: verbatim }) = command.typesetting() {
+                    command.0 = if latex { verbatim.clone() } else { plain.clone() };
+                }
+                command
+            })
+            .collect()
+    }
+
+    fn selects_term(&self) -> bool {
+        self.0.starts_with("set term")
+    }
+
+    fn selects_latex_term(&self) -> bool {
+        matches!(self.typesetting(), Some(Typesetting::LatexTerminal))
+    }
+
+    fn typesetting(&self) -> Option<&Typesetting> {
+        self.2.as_ref().and_then(|details| details.typesetting.as_ref())
+    }
+
+    fn details(&mut self) -> &mut Details {
+        self.2.get_or_insert_with(Box::default)
+    }
+
+    /// Indicates the command selects the terminal or the output file a plot is rendered to
+    pub(crate) fn selects_terminal(&self) -> bool {
+        self.selects_term() || self.selects_output()
+    }
+
+    pub(crate) fn selects_output(&self) -> bool {
+        self.0.starts_with("set output")
     }
 }
 
@@ -78,6 +230,22 @@ pub type GnuCommandFactoryResult = std::result::Result<VecDeque<GnuCommand>, Gnu
 
 pub trait GnuCommandFactory {
     fn as_commands(&self) -> GnuCommandFactoryResult;
+
+    /// Generates the commands without requiring an output file, for renders that choose the
+    /// output themselves, such as rendering into memory or checking a plot. Only commands selecting
+    /// the output differ, so this is the same as `as_commands` for everything but a `Config`.
+    fn as_commands_without_output(&self) -> GnuCommandFactoryResult {
+        self.as_commands()
+    }
+
+    /// Generates the commands for a specific version of gnuplot, failing when a command requires a
+    /// newer version instead of producing a broken plot.
+    fn as_commands_for(&self, version: Version) -> GnuCommandFactoryResult {
+        let commands = self.as_commands()?;
+        version.check(&commands)?;
+
+        Ok(commands)
+    }
 }
 
 impl<T> GnuCommandFactory for Required<T>
@@ -92,6 +260,13 @@ where
             Required::Value(value) => value.as_commands()
         }
     }
+
+    fn as_commands_without_output(&self) -> GnuCommandFactoryResult {
+        match self {
+            Required::Missing => self.as_commands(),
+            Required::Value(value) => value.as_commands_without_output()
+        }
+    }
 }
 
 impl<T> GnuCommandFactory for Maybe<T>
@@ -104,4 +279,11 @@ impl<T> GnuCommandFactory for Maybe<T>
             Maybe::Value(value) => value.as_commands()
         }
     }
+
+    fn as_commands_without_output(&self) -> GnuCommandFactoryResult {
+        match self {
+            Maybe::Nothing => Ok(vec![].into()),
+            Maybe::Value(value) => value.as_commands_without_output()
+        }
+    }
 }
\ No newline at end of file
@@ -2,6 +2,7 @@ use std::collections::VecDeque;
 use std::marker::PhantomData;
 use crate::prelude::*;
 use crate::prelude::prelude::GnuCommandFactoryResult;
+use crate::gnu::command::text::{contains_latex_math, latex_math_string};
 
 
 #[derive(Clone, PartialEq, Eq, Debug)]
@@ -63,7 +64,10 @@ where
     D: Dimension
 {
     fn as_commands(&self) -> GnuCommandFactoryResult {
-        let command = GnuCommand::new(format!("set {}label \"{}\"", D::name(), self.text));
+        let mut command = GnuCommand::new(format!("set {}label \"{}\"", D::name(), self.text));
+        if contains_latex_math(&self.text) {
+            command = command.with_latex(format!("set {}label {}", D::name(), latex_math_string(&self.text)));
+        }
         Ok(
             vec![command].into()
         )
@@ -109,6 +113,26 @@ mod tests {
         assert_eq!(commands.pop_front().unwrap().0, "set xlabel \"x\"");
     }
 
+    #[test]
+    fn test_a_label_with_latex_math_is_passed_verbatim_to_latex_terminals() {
+        let label: Label<Y> = Label::new("$\\sigma^2$ [m]");
+        let mut terminal = Tikz::default();
+        terminal.output().update("./figure.tex");
+        let mut commands = label.as_commands().unwrap();
+        commands.append(&mut Config::new(terminal).as_commands().unwrap());
+        let mut commands = GnuCommand::typeset(commands);
+
+        assert_eq!(commands.pop_front().unwrap().0, "set ylabel '$\\sigma^2$ [m]' noenhanced");
+    }
+
+    #[test]
+    fn test_a_label_with_latex_math_is_unchanged_for_other_terminals() {
+        let label: Label<Y> = Label::new("$\\sigma^2$ [m]");
+        let mut commands = GnuCommand::typeset(label.as_commands().unwrap());
+
+        assert_eq!(commands.pop_front().unwrap().0, "set ylabel \"$\\sigma^2$ [m]\"");
+    }
+
     #[test]
     fn test_a_maybe_label_has_label_information() {
         let label: Maybe<Label<X>> = Maybe::value(Label::from("x"));
@@ -1,39 +1,65 @@
 use std::collections::VecDeque;
-use crate::gnu::command::config::terminal::PngCairo;
+use crate::gnu::command::config::terminal::{PngCairo, Terminal, mark_typesetting};
 use crate::prelude::{GnuCommand, GnuCommandFactory};
 use crate::prelude::prelude::GnuCommandFactoryResult;
 
 pub mod terminal;
 pub mod filename;
 
+/// Configures how a plot is rendered. The terminal decides the output format and defaults to
+/// `PngCairo`. Another format is selected by its type, e.g. `Config<Svg>`, or at runtime using
+/// `Config<Box<dyn Terminal>>`.
 #[derive(Debug, Clone, PartialEq, Default)]
-pub struct Config {
-    terminal: PngCairo
+pub struct Config<T = PngCairo>
+where
+    T: Terminal
+{
+    terminal: T
 }
 
-impl Config {
-    pub fn terminal(&mut self) -> &mut PngCairo {
+impl<T> Config<T>
+where
+    T: Terminal
+{
+    pub fn new(terminal: T) -> Self {
+        Config {
+            terminal
+        }
+    }
+
+    pub fn terminal(&mut self) -> &mut T {
         &mut self.terminal
     }
 }
 
-impl GnuCommandFactory for Config {
+impl<T> GnuCommandFactory for Config<T>
+where
+    T: Terminal
+{
     fn as_commands(&self) -> GnuCommandFactoryResult {
         let mut commands: VecDeque<GnuCommand> = vec![].into();
-        commands.append(&mut self.terminal.as_commands()?);
+        commands.append(&mut mark_typesetting(&self.terminal, self.terminal.as_commands()?));
 
         Ok(commands)
     }
+
+    /// Only selects the terminal, so a plot without an output file can still be rendered into
+    /// memory or checked
+    fn as_commands_without_output(&self) -> GnuCommandFactoryResult {
+        Ok(mark_typesetting(&self.terminal, self.terminal.term_commands()?))
+    }
 }
 
 #[cfg(test)]
 mod tests {
+    use crate::prelude::*;
+    use crate::prelude::prelude::GnuCommandFactoryResult;
     use super::*;
 
     #[test]
     #[should_panic]
     fn test_config_creation_panics() {
-        let config = Config::default();
+        let config: Config = Config::default();
         let commands = config.as_commands();
 
         assert_eq!(commands.unwrap().pop_front().unwrap().to_string(), "set term pngcairo enhanced");
@@ -41,10 +67,102 @@ mod tests {
 
     #[test]
     fn test_config_creation_with_output() {
-        let mut config = Config::default();
+        let mut config: Config = Config::default();
         config.terminal.output().update("./some-file.png");
         let commands = config.as_commands().unwrap();
 
         assert_eq!(commands[1].to_string(), "set term pngcairo enhanced");
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_config_without_output_only_selects_the_terminal() {
+        let config: Config = Config::default();
+        let commands = config.as_commands_without_output().unwrap();
+
+        assert_eq!(commands, vec![GnuCommand::new("set term pngcairo enhanced")]);
+    }
+
+    #[test]
+    fn test_config_creation_with_svg_terminal() {
+        let mut config: Config<Svg> = Config::default();
+        config.terminal().output().update("./some-file.svg");
+        let commands = config.as_commands().unwrap();
+
+        assert_eq!(commands[0].to_string(), "set output './some-file.svg'");
+        assert_eq!(commands[1].to_string(), "set term svg enhanced");
+    }
+
+    #[test]
+    fn test_config_creation_with_pdf_cairo_terminal() {
+        let mut config: Config<PdfCairo> = Config::default();
+        config.terminal().output().update("./some-file.pdf");
+        config.terminal().size().centimetres(10.0, 7.5);
+        let commands = config.as_commands().unwrap();
+
+        assert_eq!(commands[0].to_string(), "set output './some-file.pdf'");
+        assert_eq!(commands[1].to_string(), "set term pdfcairo enhanced size 10cm,7.5cm");
+    }
+
+    #[test]
+    fn test_config_creation_with_post_script_terminal() {
+        let mut config: Config<PostScript> = Config::default();
+        config.terminal().output().update("./some-file.eps");
+        config.terminal().mode().update(PostScriptMode::Eps);
+        let commands = config.as_commands().unwrap();
+
+        assert_eq!(commands[0].to_string(), "set output './some-file.eps'");
+        assert_eq!(commands[1].to_string(), "set term postscript eps enhanced");
+    }
+
+    #[test]
+    fn test_config_creation_with_shared_terminal_options() {
+        let mut png: Config<PngCairo> = Config::default();
+        png.terminal().font().update("Helvetica", 12);
+        png.terminal().size().update(800, 600);
+        png.terminal().output().update("./some-file");
+        let mut post_script: Config<PostScript> = Config::default();
+        post_script.terminal().font().update("Helvetica", 12);
+        post_script.terminal().size().update(8, 6);
+        post_script.terminal().output().update("./some-file");
+
+        assert_eq!(png.as_commands().unwrap()[1].to_string(), "set term pngcairo enhanced size 800,600 font \"Helvetica,12\"");
+        assert_eq!(post_script.as_commands().unwrap()[1].to_string(), "set term postscript enhanced font \"Helvetica,12\" size 8in,6in");
+    }
+
+    #[test]
+    fn test_config_creation_with_canvas_terminal() {
+        let mut config: Config<Canvas> = Config::default();
+        config.terminal().output().update("./some-file.html");
+        config.terminal().mousing().update(CanvasMousing::Enabled);
+        let commands = config.as_commands().unwrap();
+
+        assert_eq!(commands[0].to_string(), "set output './some-file.html'");
+        assert_eq!(commands[1].to_string(), "set term canvas enhanced standalone mousing");
+    }
+
+    #[test]
+    fn test_config_creation_with_terminal_chosen_at_runtime() {
+        let terminals: Vec<Box<dyn Terminal>> = vec![Box::new(Svg::default()), Box::new(PdfCairo::default())];
+        for terminal in terminals {
+            let mut config = Config::new(terminal);
+            config.terminal().update_output("./some-file");
+
+            assert_eq!(config.terminal().output_filename(), Some("./some-file".into()));
+            assert!(config.clone().as_commands().is_ok());
+        }
+    }
+
+    #[test]
+    fn test_terminal_metadata() {
+        let mut terminal = PostScript::default();
+        assert_eq!(terminal.name(), "postscript");
+        assert_eq!(terminal.extension(), "ps");
+        terminal.mode().update(PostScriptMode::Eps);
+        assert_eq!(terminal.extension(), "eps");
+
+        let terminal: Box<dyn Terminal> = Box::default();
+        assert_eq!(terminal.mime_type(), "image/png");
+        assert!(Dumb::default().capabilities().text);
+        assert!(GifAnimate::default().capabilities().animation);
+    }
+}
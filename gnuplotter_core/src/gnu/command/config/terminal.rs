@@ -1,12 +1,179 @@
 use std::collections::VecDeque;
-use std::fmt::{Display, Formatter};
+use std::any::Any;
+use std::fmt::{Debug, Display, Formatter};
 use crate::prelude::*;
 use crate::prelude::prelude::GnuCommandFactoryResult;
 
-trait Terminal : GnuCommandFactory {}
+pub mod svg;
+pub mod pdf_cairo;
+pub mod dumb;
+pub mod post_script;
+pub mod latex;
+pub mod canvas;
+pub mod gif_animate;
+pub mod sixel;
 
+/// Describes what a terminal is able to produce, so a renderer can decide how to use it
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct TerminalCapabilities {
+    /// The plot can be written to STDOUT by setting the output to `Output::Stdout`
+    pub stdout: bool,
+    /// The plot is written as text rather than as binary data
+    pub text: bool,
+    /// The plot is a vector image which scales without loss of quality
+    pub vector: bool,
+    /// Every plot command adds a frame to a single animated output
+    pub animation: bool,
+    /// Text in the plot is typeset by LaTeX
+    pub latex: bool,
+    /// The plot responds to the mouse when viewed
+    pub interactive: bool
+}
+
+/// A gnuplot terminal decides the format a plot is rendered to. Its commands select the terminal
+/// and, when set, the output file. Implement it to use a terminal gnuplotter does not ship with in
+/// a `Config`, building its commands using `GnuCommand::new` and implementing `as_commands` using
+/// `terminal_as_commands`.
+pub trait Terminal : GnuCommandFactory + Debug + TerminalClone + TerminalEq {
+    /// Name gnuplot uses for the terminal in `set term`
+    fn name(&self) -> &'static str;
+
+    /// Extension of the files written by the terminal, without a leading dot
+    fn extension(&self) -> &'static str;
+
+    /// MIME type of the rendered output
+    fn mime_type(&self) -> &'static str;
+
+    fn capabilities(&self) -> TerminalCapabilities;
+
+    /// Where the plot is written to
+    fn terminal_output(&self) -> &Output;
+
+    fn terminal_output_mut(&mut self) -> &mut Output;
+
+    /// The file the plot will be written to, if any
+    fn output_filename(&self) -> Option<String> {
+        self.terminal_output().filename()
+    }
+
+    fn update_output(&mut self, filename: &str) {
+        self.terminal_output_mut().update(filename);
+    }
+
+    /// The commands selecting the terminal, without those selecting the output. These are
+    /// generated even when the output is missing.
+    fn term_commands(&self) -> GnuCommandFactoryResult;
+}
+
+/// Allows a boxed terminal to be cloned. It is implemented for every terminal that is `Clone`.
+pub trait TerminalClone {
+    fn clone_box(&self) -> Box<dyn Terminal>;
+}
+
+impl<T> TerminalClone for T
+where
+    T: Terminal + Clone + 'static
+{
+    fn clone_box(&self) -> Box<dyn Terminal> {
+        Box::new(self.clone())
+    }
+}
+
+/// Allows boxed terminals to be compared. It is implemented for every terminal that is `PartialEq`,
+/// and terminals of different types are never equal.
+pub trait TerminalEq {
+    fn as_any(&self) -> &dyn Any;
+
+    fn eq_box(&self, other: &dyn Terminal) -> bool;
+}
+
+impl<T> TerminalEq for T
+where
+    T: Terminal + PartialEq + 'static
+{
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn eq_box(&self, other: &dyn Terminal) -> bool {
+        other.as_any().downcast_ref::<T>().is_some_and(|other| self == other)
+    }
+}
+
+impl PartialEq for Box<dyn Terminal> {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_ref().eq_box(other.as_ref())
+    }
+}
+
+impl Clone for Box<dyn Terminal> {
+    fn clone(&self) -> Self {
+        self.as_ref().clone_box()
+    }
+}
+
+impl Default for Box<dyn Terminal> {
+    fn default() -> Self {
+        Box::new(PngCairo::default())
+    }
+}
+
+impl GnuCommandFactory for Box<dyn Terminal> {
+    fn as_commands(&self) -> GnuCommandFactoryResult {
+        self.as_ref().as_commands()
+    }
+}
+
+/// A boxed terminal is a terminal chosen at runtime: `Config<Box<dyn Terminal>>`
+impl Terminal for Box<dyn Terminal> {
+    fn name(&self) -> &'static str {
+        self.as_ref().name()
+    }
+
+    fn extension(&self) -> &'static str {
+        self.as_ref().extension()
+    }
+
+    fn mime_type(&self) -> &'static str {
+        self.as_ref().mime_type()
+    }
+
+    fn capabilities(&self) -> TerminalCapabilities {
+        self.as_ref().capabilities()
+    }
+
+    fn terminal_output(&self) -> &Output {
+        self.as_ref().terminal_output()
+    }
+
+    fn terminal_output_mut(&mut self) -> &mut Output {
+        self.as_mut().terminal_output_mut()
+    }
+
+    fn term_commands(&self) -> GnuCommandFactoryResult {
+        self.as_ref().term_commands()
+    }
+}
+
+/// Physical unit in which the size of a page is expressed by vector terminals
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SizeUnit {
+    Inches,
+    Centimetres
+}
+
+impl Display for SizeUnit {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SizeUnit::Inches => f.write_str("in"),
+            SizeUnit::Centimetres => f.write_str("cm")
+        }
+    }
+}
+
+/// Font of the text in a plot
 #[derive(Debug, Clone, PartialEq, Default)]
-pub enum PngCairoFont {
+pub enum Font {
     #[default]
     Default,
     Custom {
@@ -15,24 +182,24 @@ pub enum PngCairoFont {
     }
 }
 
-impl PngCairoFont {
+impl Font {
     pub fn custom(name: &str, size: usize) -> Self {
-        PngCairoFont::Custom {
+        Font::Custom {
             name: name.into(),
             size: size as u32
         }
     }
 
     pub fn update(&mut self, name: &str, size: usize) {
-        std::mem::swap(self, &mut PngCairoFont::custom(name, size));
+        *self = Font::custom(name, size);
     }
 }
 
-impl Display for PngCairoFont {
+impl Display for Font {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         match self {
-            PngCairoFont::Default => f.write_str(""),
-            PngCairoFont::Custom { name, size} => {
+            Font::Default => f.write_str(""),
+            Font::Custom { name, size} => {
                 f.write_fmt(
                     format_args!("font \"{},{}\"", name, size)
                 )
@@ -41,8 +208,9 @@ impl Display for PngCairoFont {
     }
 }
 
+/// Size of a plot in pixels, or in characters for text plots
 #[derive(Debug, Clone, PartialEq, Default)]
-pub enum PngCairoSize {
+pub enum Size {
     #[default]
     Missing,
     Value {
@@ -51,17 +219,17 @@ pub enum PngCairoSize {
     }
 }
 
-impl PngCairoSize {
+impl Size {
     pub fn update(&mut self, width: usize, height: usize) {
-        std::mem::swap(self, &mut PngCairoSize::Value { width, height });
+        *self = Size::Value { width, height };
     }
 }
 
-impl Display for PngCairoSize {
+impl Display for Size {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         match self {
-            PngCairoSize::Missing => f.write_str(""),
-            PngCairoSize::Value { width, height} => {
+            Size::Missing => f.write_str(""),
+            Size::Value { width, height} => {
                 f.write_fmt(
                     format_args!("size {},{}", width, height)
                 )
@@ -70,60 +238,190 @@ impl Display for PngCairoSize {
     }
 }
 
+/// Size of a plot on a page, used by terminals measuring their output in physical units
+#[derive(Debug, Clone, PartialEq, Default)]
+pub enum PageSize {
+    #[default]
+    Missing,
+    Value {
+        width: f64,
+        height: f64,
+        unit: SizeUnit
+    }
+}
+
+impl PageSize {
+    /// Sets the size in inches, which is the unit gnuplot uses by default
+    pub fn update<W, H>(&mut self, width: W, height: H)
+    where
+        W: Into<f64>,
+        H: Into<f64>
+    {
+        self.inches(width.into(), height.into());
+    }
+
+    pub fn inches(&mut self, width: f64, height: f64) {
+        *self = PageSize::Value { width, height, unit: SizeUnit::Inches };
+    }
+
+    pub fn centimetres(&mut self, width: f64, height: f64) {
+        *self = PageSize::Value { width, height, unit: SizeUnit::Centimetres };
+    }
+}
+
+impl Display for PageSize {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PageSize::Missing => f.write_str(""),
+            PageSize::Value { width, height, unit } => {
+                f.write_fmt(
+                    format_args!("size {}{},{}{}", width, unit, height, unit)
+                )
+            }
+        }
+    }
+}
+
+/// Where a terminal writes the plot to. Terminals able to write to STDOUT do so when set to
+/// `Stdout`, every other terminal requires a file.
 #[derive(Clone, Debug, PartialEq, Default)]
-pub enum PngCairoOutput {
+pub enum Output {
     #[default]
     Missing,
+    Stdout,
     Filename(String)
 }
 
-impl PngCairoOutput {
+impl Output {
     pub fn update(&mut self, filename: &str) {
-        std::mem::swap(self, &mut PngCairoOutput::Filename(filename.into()))
+        *self = Output::Filename(filename.into());
     }
-}
 
-impl GnuCommandFactory for PngCairoOutput {
-    fn as_commands(&self) -> GnuCommandFactoryResult {
+    pub fn write_to_stdout(&mut self) {
+        *self = Output::Stdout;
+    }
+
+    pub fn filename(&self) -> Option<String> {
         match self {
-            PngCairoOutput::Missing =>
-                panic!("PNGCairo requires an output file: TERMINAL.output().update(\"./filename.png\")"),
-            PngCairoOutput::Filename(filename) => {
-                Ok(vec![GnuCommand::new(format!("set output '{}'", filename))].into())
-            }
+            Output::Filename(filename) => Some(filename.clone()),
+            _ => None
+        }
+    }
+}
+
+/// Generates the commands of a terminal, which are the `set output` command followed by the
+/// commands selecting the terminal. Fails when the terminal has nowhere to write the plot to. The
+/// terminal is called `label` in errors. Terminals implement `as_commands` using it, including
+/// those defined outside gnuplotter.
+pub fn terminal_as_commands(terminal: &dyn Terminal, label: &str) -> GnuCommandFactoryResult {
+    let mut commands = output_commands(terminal, label)?;
+    commands.append(&mut terminal.term_commands()?);
+
+    Ok(commands)
+}
+
+/// Joins the options of a `set term` command, leaving out the options that aren't set
+pub(crate) fn join_options(options: &[String]) -> String {
+    options
+        .iter()
+        .filter(|option| !option.is_empty())
+        .cloned()
+        .collect::<Vec<String>>()
+        .join(" ")
+}
+
+/// Generates the `set output` command of a terminal, failing when it has nowhere to write the plot
+/// to
+fn output_commands(terminal: &dyn Terminal, label: &str) -> GnuCommandFactoryResult {
+    let missing = |reason: &str| GnuCommandFactoryError::required_value_missing(&format!(
+        "{} {}: TERMINAL.output().update(\"./filename.{}\")",
+        label, reason, terminal.extension()
+    ));
+
+    match terminal.terminal_output() {
+        Output::Missing => Err(missing("requires an output file")),
+        Output::Stdout if !terminal.capabilities().stdout => Err(missing("can't write to STDOUT and requires an output file")),
+        Output::Stdout => Ok(vec![].into()),
+        Output::Filename(filename) => {
+            Ok(vec![GnuCommand::new(format!("set output '{}'", filename))].into())
         }
     }
 }
 
+/// Marks the commands of a terminal typesetting text using LaTeX, so titles and labels can be
+/// written for it
+pub(crate) fn mark_typesetting(terminal: &dyn Terminal, commands: VecDeque<GnuCommand>) -> VecDeque<GnuCommand> {
+    if !terminal.capabilities().latex {
+        return commands;
+    }
+
+    commands
+        .into_iter()
+        .map(|command| if command.selects_output() { command } else { command.typesetting_latex() })
+        .collect()
+}
+
+/// The sub-builders of `PngCairo` by the names they had before other terminals shared them
+pub type PngCairoFont = Font;
+pub type PngCairoSize = Size;
+pub type PngCairoOutput = Output;
+
 #[derive(Debug, Clone, PartialEq, Default)]
 pub struct PngCairo {
-    size: PngCairoSize,
-    font: PngCairoFont,
-    output: PngCairoOutput
+    size: Size,
+    font: Font,
+    output: Output
 }
 
 impl PngCairo {
-    pub fn font(&mut self) -> &mut PngCairoFont {
+    pub fn font(&mut self) -> &mut Font {
         &mut self.font
     }
 
-    pub fn size(&mut self) -> &mut PngCairoSize {
+    pub fn size(&mut self) -> &mut Size {
         &mut self.size
     }
 
-    pub fn output(&mut self) -> &mut PngCairoOutput {
+    pub fn output(&mut self) -> &mut Output {
         &mut self.output
     }
 }
 
-impl Terminal for PngCairo {}
+impl Terminal for PngCairo {
+    fn name(&self) -> &'static str {
+        "pngcairo"
+    }
 
-impl GnuCommandFactory for PngCairo {
-    fn as_commands(&self) -> GnuCommandFactoryResult {
+    fn extension(&self) -> &'static str {
+        "png"
+    }
+
+    fn mime_type(&self) -> &'static str {
+        "image/png"
+    }
+
+    fn capabilities(&self) -> TerminalCapabilities {
+        TerminalCapabilities {
+            stdout: true,
+            ..Default::default()
+        }
+    }
+
+    fn terminal_output(&self) -> &Output {
+        &self.output
+    }
+
+    fn terminal_output_mut(&mut self) -> &mut Output {
+        &mut self.output
+    }
+
+    fn term_commands(&self) -> GnuCommandFactoryResult {
         let mut commands = VecDeque::new();
-        commands.append(&mut self.output.as_commands()?);
         commands.push_back(
-            GnuCommand::new(format!("set term pngcairo enhanced {} {}", self.size, self.font).trim())
+            GnuCommand::new(format!(
+                "set term pngcairo enhanced {}",
+                join_options(&[self.size.to_string(), self.font.to_string()])
+            ).trim())
         );
 
 
@@ -131,10 +429,24 @@ impl GnuCommandFactory for PngCairo {
     }
 }
 
+impl GnuCommandFactory for PngCairo {
+    fn as_commands(&self) -> GnuCommandFactoryResult {
+        terminal_as_commands(self, "PNGCairo")
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_options_that_are_not_set_are_left_out() {
+        let mut terminal = PngCairo::default();
+        terminal.font().update("Helvetica", 14);
+
+        assert_eq!(terminal.term_commands().unwrap()[0].to_string(), "set term pngcairo enhanced font \"Helvetica,14\"");
+    }
+
     #[test]
     fn test_png_cairo_terminal_creation() {
         let mut terminal = PngCairo::default();
@@ -159,6 +471,46 @@ mod tests {
         assert_eq!(commands[1].to_string(), "set term pngcairo enhanced");
     }
 
+    #[test]
+    fn test_png_cairo_terminal_creation_writing_to_stdout() {
+        let mut terminal = PngCairo::default();
+        terminal.output().write_to_stdout();
+        let commands = terminal.as_commands().unwrap();
+
+        assert_eq!(commands.len(), 1);
+        assert_eq!(commands[0].to_string(), "set term pngcairo enhanced");
+    }
+
+    #[test]
+    fn test_term_commands_do_not_require_an_output() {
+        let mut terminal = PngCairo::default();
+        terminal.size().update(1200, 800);
+        let commands = terminal.term_commands().unwrap();
+
+        assert_eq!(commands.len(), 1);
+        assert_eq!(commands[0].to_string(), "set term pngcairo enhanced size 1200,800");
+    }
+
+    #[test]
+    fn test_writing_to_stdout_requires_a_capable_terminal() {
+        let mut terminal = GifAnimate::default();
+        terminal.output().write_to_stdout();
+
+        assert!(terminal.as_commands().is_err());
+    }
+
+    #[test]
+    fn test_boxed_terminals_are_compared_by_type_and_value() {
+        let png: Box<dyn Terminal> = Box::new(PngCairo::default());
+        let svg: Box<dyn Terminal> = Box::new(Svg::default());
+        let mut other_png = PngCairo::default();
+
+        assert!(png == Box::new(other_png.clone()) as Box<dyn Terminal>);
+        assert!(png != svg);
+        other_png.size().update(800, 600);
+        assert_ne!(Config::new(png), Config::new(Box::new(other_png) as Box<dyn Terminal>));
+    }
+
     #[test]
     #[should_panic(expected = "PNGCairo requires an output file: TERMINAL.output().update(\"./filename.png\")")]
     fn test_png_cairo_terminal_creation_without_filename_panics() {
@@ -0,0 +1,179 @@
+use std::collections::VecDeque;
+use std::fmt::{Display, Formatter};
+use crate::prelude::*;
+use crate::prelude::prelude::GnuCommandFactoryResult;
+use super::{Font, Output, Size, Terminal, TerminalCapabilities, join_options, terminal_as_commands};
+
+/// Enables zooming and hovering over the plot in the browser
+#[derive(Debug, Clone, PartialEq, Default)]
+pub enum CanvasMousing {
+    #[default]
+    Disabled,
+    Enabled
+}
+
+impl CanvasMousing {
+    pub fn update(&mut self, mousing: CanvasMousing) {
+        *self = mousing;
+    }
+}
+
+impl Display for CanvasMousing {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CanvasMousing::Disabled => f.write_str(""),
+            CanvasMousing::Enabled => f.write_str("mousing")
+        }
+    }
+}
+
+/// Location the page loads gnuplot's javascript files from. Gnuplot's own installation directory
+/// is used by default, which only works on machines where gnuplot is installed.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub enum CanvasJsDir {
+    #[default]
+    Default,
+    Path(String)
+}
+
+impl CanvasJsDir {
+    pub fn update(&mut self, path: &str) {
+        *self = CanvasJsDir::Path(path.into());
+    }
+}
+
+impl Display for CanvasJsDir {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CanvasJsDir::Default => f.write_str(""),
+            CanvasJsDir::Path(path) => {
+                f.write_fmt(
+                    format_args!("jsdir '{}'", path)
+                )
+            }
+        }
+    }
+}
+
+/// Renders the plot as a standalone HTML page drawing on an HTML5 canvas
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Canvas {
+    size: Size,
+    font: Font,
+    mousing: CanvasMousing,
+    js_dir: CanvasJsDir,
+    output: Output
+}
+
+impl Canvas {
+    pub fn size(&mut self) -> &mut Size {
+        &mut self.size
+    }
+
+    pub fn font(&mut self) -> &mut Font {
+        &mut self.font
+    }
+
+    pub fn mousing(&mut self) -> &mut CanvasMousing {
+        &mut self.mousing
+    }
+
+    pub fn js_dir(&mut self) -> &mut CanvasJsDir {
+        &mut self.js_dir
+    }
+
+    pub fn output(&mut self) -> &mut Output {
+        &mut self.output
+    }
+}
+
+impl Terminal for Canvas {
+    fn name(&self) -> &'static str {
+        "canvas"
+    }
+
+    fn extension(&self) -> &'static str {
+        "html"
+    }
+
+    fn mime_type(&self) -> &'static str {
+        "text/html"
+    }
+
+    fn capabilities(&self) -> TerminalCapabilities {
+        TerminalCapabilities {
+            stdout: true,
+            text: true,
+            interactive: true,
+            ..Default::default()
+        }
+    }
+
+    fn terminal_output(&self) -> &Output {
+        &self.output
+    }
+
+    fn terminal_output_mut(&mut self) -> &mut Output {
+        &mut self.output
+    }
+
+    fn term_commands(&self) -> GnuCommandFactoryResult {
+        let options = [
+            self.mousing.to_string(),
+            self.size.to_string(),
+            self.font.to_string(),
+            self.js_dir.to_string()
+        ];
+        let options = join_options(&options);
+
+        let mut commands = VecDeque::new();
+        commands.push_back(
+            GnuCommand::new(format!("set term canvas enhanced standalone {}", options).trim())
+        );
+
+        Ok(commands)
+    }
+}
+
+impl GnuCommandFactory for Canvas {
+    fn as_commands(&self) -> GnuCommandFactoryResult {
+        terminal_as_commands(self, "Canvas")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_canvas_terminal_creation() {
+        let mut terminal = Canvas::default();
+        terminal.size().update(800, 600);
+        terminal.font().update("Arial", 10);
+        terminal.mousing().update(CanvasMousing::Enabled);
+        terminal.js_dir().update("https://example.org/gnuplot/js");
+        terminal.output().update("./result.html");
+        let commands = terminal.as_commands().unwrap();
+
+        assert_eq!(commands.len(), 2);
+        assert_eq!(commands[0].to_string(), "set output './result.html'");
+        assert_eq!(commands[1].to_string(), "set term canvas enhanced standalone mousing size 800,600 font \"Arial,10\" jsdir 'https://example.org/gnuplot/js'");
+    }
+
+    #[test]
+    fn test_canvas_terminal_empty_creation() {
+        let mut terminal = Canvas::default();
+        terminal.output().update("./result.html");
+        let commands = terminal.as_commands().unwrap();
+
+        assert_eq!(commands.len(), 2);
+        assert_eq!(commands[1].to_string(), "set term canvas enhanced standalone");
+    }
+
+    #[test]
+    fn test_canvas_terminal_creation_without_filename_fails() {
+        let terminal = Canvas::default();
+
+        assert!(terminal.as_commands().is_err());
+    }
+}
@@ -0,0 +1,189 @@
+use std::collections::VecDeque;
+use std::fmt::{Display, Formatter};
+use crate::prelude::*;
+use crate::prelude::prelude::GnuCommandFactoryResult;
+use super::{Output, Size, Terminal, TerminalCapabilities, join_options, terminal_as_commands};
+
+/// Decides whether gnuplot emits a form feed after each plot
+#[derive(Debug, Clone, PartialEq, Default)]
+pub enum DumbFeed {
+    #[default]
+    Default,
+    Feed,
+    NoFeed
+}
+
+impl DumbFeed {
+    pub fn update(&mut self, feed: DumbFeed) {
+        *self = feed;
+    }
+}
+
+impl Display for DumbFeed {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DumbFeed::Default => f.write_str(""),
+            DumbFeed::Feed => f.write_str("feed"),
+            DumbFeed::NoFeed => f.write_str("nofeed")
+        }
+    }
+}
+
+/// Colors the text plot using ANSI escape sequences
+#[derive(Debug, Clone, PartialEq, Default)]
+pub enum DumbColor {
+    #[default]
+    Default,
+    Mono,
+    Ansi,
+    Ansi256,
+    AnsiRgb
+}
+
+impl DumbColor {
+    pub fn update(&mut self, color: DumbColor) {
+        *self = color;
+    }
+
+    /// ANSI colors were added to the dumb terminal in gnuplot 5.2
+    fn required_version(&self) -> Option<Version> {
+        match self {
+            DumbColor::Ansi | DumbColor::Ansi256 | DumbColor::AnsiRgb => Some(Version::new(5, 2, 0)),
+            _ => None
+        }
+    }
+}
+
+impl Display for DumbColor {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DumbColor::Default => f.write_str(""),
+            DumbColor::Mono => f.write_str("mono"),
+            DumbColor::Ansi => f.write_str("ansi"),
+            DumbColor::Ansi256 => f.write_str("ansi256"),
+            DumbColor::AnsiRgb => f.write_str("ansirgb")
+        }
+    }
+}
+
+/// Renders the plot as text. It is written to STDOUT unless a file is given, which allows it to be
+/// captured using `CanRender::render_to_string()`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Dumb {
+    size: Size,
+    feed: DumbFeed,
+    color: DumbColor,
+    output: Output
+}
+
+impl Default for Dumb {
+    fn default() -> Self {
+        Dumb {
+            size: Size::default(),
+            feed: DumbFeed::default(),
+            color: DumbColor::default(),
+            output: Output::Stdout
+        }
+    }
+}
+
+impl Dumb {
+    pub fn size(&mut self) -> &mut Size {
+        &mut self.size
+    }
+
+    pub fn feed(&mut self) -> &mut DumbFeed {
+        &mut self.feed
+    }
+
+    pub fn color(&mut self) -> &mut DumbColor {
+        &mut self.color
+    }
+
+    pub fn output(&mut self) -> &mut Output {
+        &mut self.output
+    }
+}
+
+impl Terminal for Dumb {
+    fn name(&self) -> &'static str {
+        "dumb"
+    }
+
+    fn extension(&self) -> &'static str {
+        "txt"
+    }
+
+    fn mime_type(&self) -> &'static str {
+        "text/plain"
+    }
+
+    fn capabilities(&self) -> TerminalCapabilities {
+        TerminalCapabilities {
+            stdout: true,
+            text: true,
+            ..Default::default()
+        }
+    }
+
+    fn terminal_output(&self) -> &Output {
+        &self.output
+    }
+
+    fn terminal_output_mut(&mut self) -> &mut Output {
+        &mut self.output
+    }
+
+    fn term_commands(&self) -> GnuCommandFactoryResult {
+        let options = [
+            self.size.to_string(),
+            self.feed.to_string(),
+            self.color.to_string()
+        ];
+        let options = join_options(&options);
+
+        let mut commands = VecDeque::new();
+        let mut terminal = GnuCommand::new(format!("set term dumb enhanced {}", options).trim());
+        if let Some(version) = self.color.required_version() {
+            terminal = terminal.requires(version);
+        }
+        commands.push_back(terminal);
+
+        Ok(commands)
+    }
+}
+
+impl GnuCommandFactory for Dumb {
+    fn as_commands(&self) -> GnuCommandFactoryResult {
+        terminal_as_commands(self, "Dumb")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dumb_terminal_creation() {
+        let mut terminal = Dumb::default();
+        terminal.size().update(80, 25);
+        terminal.feed().update(DumbFeed::NoFeed);
+        terminal.color().update(DumbColor::Ansi256);
+        let commands = terminal.as_commands().unwrap();
+
+        assert_eq!(commands.len(), 1);
+        assert_eq!(commands[0].to_string(), "set term dumb enhanced size 80,25 nofeed ansi256");
+        assert_eq!(commands[0].required_version(), Some(Version::new(5, 2, 0)));
+    }
+
+    #[test]
+    fn test_dumb_terminal_creation_with_filename() {
+        let mut terminal = Dumb::default();
+        terminal.output().update("./result.txt");
+        let commands = terminal.as_commands().unwrap();
+
+        assert_eq!(commands.len(), 2);
+        assert_eq!(commands[0].to_string(), "set output './result.txt'");
+        assert_eq!(commands[1].to_string(), "set term dumb enhanced");
+    }
+}
@@ -0,0 +1,216 @@
+use std::collections::VecDeque;
+use std::fmt::{Display, Formatter};
+use crate::prelude::*;
+use crate::prelude::prelude::GnuCommandFactoryResult;
+use super::{Font, Output, Size, Terminal, TerminalCapabilities, join_options, terminal_as_commands};
+
+/// Time between two frames in hundredths of a second
+#[derive(Debug, Clone, PartialEq, Default)]
+pub enum GifAnimateDelay {
+    #[default]
+    Default,
+    Value(u32)
+}
+
+impl GifAnimateDelay {
+    pub fn update(&mut self, delay: u32) {
+        *self = GifAnimateDelay::Value(delay);
+    }
+}
+
+impl Display for GifAnimateDelay {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GifAnimateDelay::Default => f.write_str(""),
+            GifAnimateDelay::Value(delay) => {
+                f.write_fmt(
+                    format_args!("delay {}", delay)
+                )
+            }
+        }
+    }
+}
+
+/// Number of times the animation is played
+#[derive(Debug, Clone, PartialEq, Default)]
+pub enum GifAnimateLoop {
+    #[default]
+    Default,
+    Forever,
+    Times(u32)
+}
+
+impl GifAnimateLoop {
+    pub fn update(&mut self, count: GifAnimateLoop) {
+        *self = count;
+    }
+}
+
+impl Display for GifAnimateLoop {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GifAnimateLoop::Default => f.write_str(""),
+            GifAnimateLoop::Forever => f.write_str("loop 0"),
+            GifAnimateLoop::Times(count) => {
+                f.write_fmt(
+                    format_args!("loop {}", count)
+                )
+            }
+        }
+    }
+}
+
+/// Optimization shrinks the file by only storing what changes between frames, but is known to
+/// produce color artifacts in some cases.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub enum GifAnimateOptimize {
+    #[default]
+    Default,
+    Optimize,
+    NoOptimize
+}
+
+impl GifAnimateOptimize {
+    pub fn update(&mut self, optimize: GifAnimateOptimize) {
+        *self = optimize;
+    }
+}
+
+impl Display for GifAnimateOptimize {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GifAnimateOptimize::Default => f.write_str(""),
+            GifAnimateOptimize::Optimize => f.write_str("optimize"),
+            GifAnimateOptimize::NoOptimize => f.write_str("nooptimize")
+        }
+    }
+}
+
+/// Renders every `plot` command as a frame of an animated gif. Frames are best rendered using
+/// `CanRender::render_animation()`.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct GifAnimate {
+    size: Size,
+    font: Font,
+    delay: GifAnimateDelay,
+    loop_count: GifAnimateLoop,
+    optimize: GifAnimateOptimize,
+    output: Output
+}
+
+impl GifAnimate {
+    pub fn size(&mut self) -> &mut Size {
+        &mut self.size
+    }
+
+    pub fn font(&mut self) -> &mut Font {
+        &mut self.font
+    }
+
+    pub fn delay(&mut self) -> &mut GifAnimateDelay {
+        &mut self.delay
+    }
+
+    pub fn loop_count(&mut self) -> &mut GifAnimateLoop {
+        &mut self.loop_count
+    }
+
+    pub fn optimize(&mut self) -> &mut GifAnimateOptimize {
+        &mut self.optimize
+    }
+
+    pub fn output(&mut self) -> &mut Output {
+        &mut self.output
+    }
+}
+
+impl Terminal for GifAnimate {
+    fn name(&self) -> &'static str {
+        "gif"
+    }
+
+    fn extension(&self) -> &'static str {
+        "gif"
+    }
+
+    fn mime_type(&self) -> &'static str {
+        "image/gif"
+    }
+
+    fn capabilities(&self) -> TerminalCapabilities {
+        TerminalCapabilities {
+            animation: true,
+            ..Default::default()
+        }
+    }
+
+    fn terminal_output(&self) -> &Output {
+        &self.output
+    }
+
+    fn terminal_output_mut(&mut self) -> &mut Output {
+        &mut self.output
+    }
+
+    fn term_commands(&self) -> GnuCommandFactoryResult {
+        let options = [
+            self.size.to_string(),
+            self.font.to_string(),
+            "animate".to_string(),
+            self.delay.to_string(),
+            self.loop_count.to_string(),
+            self.optimize.to_string()
+        ];
+        let options = join_options(&options);
+
+        let mut commands = VecDeque::new();
+        commands.push_back(
+            GnuCommand::new(format!("set term gif enhanced {}", options))
+        );
+
+        Ok(commands)
+    }
+}
+
+impl GnuCommandFactory for GifAnimate {
+    fn as_commands(&self) -> GnuCommandFactoryResult {
+        terminal_as_commands(self, "GifAnimate")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gif_animate_terminal_creation() {
+        let mut terminal = GifAnimate::default();
+        terminal.size().update(640, 480);
+        terminal.delay().update(10);
+        terminal.loop_count().update(GifAnimateLoop::Forever);
+        terminal.optimize().update(GifAnimateOptimize::Optimize);
+        terminal.output().update("./result.gif");
+        let commands = terminal.as_commands().unwrap();
+
+        assert_eq!(commands.len(), 2);
+        assert_eq!(commands[0].to_string(), "set output './result.gif'");
+        assert_eq!(commands[1].to_string(), "set term gif enhanced size 640,480 animate delay 10 loop 0 optimize");
+    }
+
+    #[test]
+    fn test_gif_animate_terminal_empty_creation() {
+        let mut terminal = GifAnimate::default();
+        terminal.loop_count().update(GifAnimateLoop::Times(3));
+        terminal.output().update("./result.gif");
+        let commands = terminal.as_commands().unwrap();
+
+        assert_eq!(commands[1].to_string(), "set term gif enhanced animate loop 3");
+    }
+
+    #[test]
+    fn test_gif_animate_terminal_creation_without_filename_fails() {
+        let terminal = GifAnimate::default();
+
+        assert!(terminal.as_commands().is_err());
+    }
+}
@@ -0,0 +1,330 @@
+//! Terminals writing plots as LaTeX, so text and math in a plot is typeset by the document that
+//! includes it. Titles and labels containing `$...$` math are passed to these terminals verbatim.
+
+use std::collections::VecDeque;
+use std::fmt::{Display, Formatter};
+use crate::prelude::*;
+use crate::prelude::prelude::GnuCommandFactoryResult;
+use super::{Output, PageSize, Terminal, TerminalCapabilities, join_options, terminal_as_commands};
+
+/// Decides whether the generated `.tex` file is meant to be `\input` into a document or can be
+/// compiled on its own
+#[derive(Debug, Clone, PartialEq, Default)]
+pub enum LatexDocument {
+    #[default]
+    Input,
+    Standalone
+}
+
+impl LatexDocument {
+    pub fn update(&mut self, document: LatexDocument) {
+        *self = document;
+    }
+}
+
+impl Display for LatexDocument {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LatexDocument::Input => f.write_str("input"),
+            LatexDocument::Standalone => f.write_str("standalone")
+        }
+    }
+}
+
+/// LaTeX code added to the preamble of the generated file, such as `\usepackage{amsmath}`
+#[derive(Debug, Clone, PartialEq, Default)]
+pub enum LatexHeader {
+    #[default]
+    Default,
+    NoHeader,
+    Custom(String)
+}
+
+impl LatexHeader {
+    pub fn update(&mut self, header: &str) {
+        *self = LatexHeader::Custom(header.into());
+    }
+}
+
+impl Display for LatexHeader {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LatexHeader::Default => f.write_str(""),
+            LatexHeader::NoHeader => f.write_str("noheader"),
+            LatexHeader::Custom(header) => {
+                f.write_fmt(
+                    format_args!("header '{}'", header.replace('\'', "''"))
+                )
+            }
+        }
+    }
+}
+
+/// Format of the graphics file written next to the `.tex` file by `CairoLatex`
+#[derive(Debug, Clone, PartialEq, Default)]
+pub enum CairoLatexGraphics {
+    #[default]
+    Pdf,
+    Eps
+}
+
+impl CairoLatexGraphics {
+    pub fn update(&mut self, graphics: CairoLatexGraphics) {
+        *self = graphics;
+    }
+}
+
+impl Display for CairoLatexGraphics {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CairoLatexGraphics::Pdf => f.write_str("pdf"),
+            CairoLatexGraphics::Eps => f.write_str("eps")
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct CairoLatex {
+    graphics: CairoLatexGraphics,
+    document: LatexDocument,
+    header: LatexHeader,
+    size: PageSize,
+    output: Output
+}
+
+impl CairoLatex {
+    pub fn graphics(&mut self) -> &mut CairoLatexGraphics {
+        &mut self.graphics
+    }
+
+    pub fn document(&mut self) -> &mut LatexDocument {
+        &mut self.document
+    }
+
+    pub fn header(&mut self) -> &mut LatexHeader {
+        &mut self.header
+    }
+
+    pub fn size(&mut self) -> &mut PageSize {
+        &mut self.size
+    }
+
+    /// The `.tex` file to write. Graphics are written next to it using the same name, but with the
+    /// extension of their format.
+    pub fn output(&mut self) -> &mut Output {
+        &mut self.output
+    }
+}
+
+impl Terminal for CairoLatex {
+    fn name(&self) -> &'static str {
+        "cairolatex"
+    }
+
+    fn extension(&self) -> &'static str {
+        "tex"
+    }
+
+    fn mime_type(&self) -> &'static str {
+        "application/x-tex"
+    }
+
+    fn capabilities(&self) -> TerminalCapabilities {
+        TerminalCapabilities {
+            vector: true,
+            latex: true,
+            ..Default::default()
+        }
+    }
+
+    fn terminal_output(&self) -> &Output {
+        &self.output
+    }
+
+    fn terminal_output_mut(&mut self) -> &mut Output {
+        &mut self.output
+    }
+
+    fn term_commands(&self) -> GnuCommandFactoryResult {
+        let options = [
+            self.graphics.to_string(),
+            self.document.to_string(),
+            self.header.to_string(),
+            self.size.to_string()
+        ];
+        let options = join_options(&options);
+
+        let mut commands = VecDeque::new();
+        commands.push_back(
+            GnuCommand::new(format!("set term cairolatex {}", options))
+        );
+
+        Ok(commands)
+    }
+}
+
+impl GnuCommandFactory for CairoLatex {
+    fn as_commands(&self) -> GnuCommandFactoryResult {
+        terminal_as_commands(self, "CairoLatex")
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub enum TikzColor {
+    #[default]
+    Default,
+    Color,
+    Monochrome
+}
+
+impl TikzColor {
+    pub fn update(&mut self, color: TikzColor) {
+        *self = color;
+    }
+}
+
+impl Display for TikzColor {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TikzColor::Default => f.write_str(""),
+            TikzColor::Color => f.write_str("color"),
+            TikzColor::Monochrome => f.write_str("monochrome")
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Tikz {
+    color: TikzColor,
+    document: LatexDocument,
+    header: LatexHeader,
+    size: PageSize,
+    output: Output
+}
+
+impl Tikz {
+    pub fn color(&mut self) -> &mut TikzColor {
+        &mut self.color
+    }
+
+    pub fn document(&mut self) -> &mut LatexDocument {
+        &mut self.document
+    }
+
+    pub fn header(&mut self) -> &mut LatexHeader {
+        &mut self.header
+    }
+
+    pub fn size(&mut self) -> &mut PageSize {
+        &mut self.size
+    }
+
+    pub fn output(&mut self) -> &mut Output {
+        &mut self.output
+    }
+}
+
+impl Terminal for Tikz {
+    fn name(&self) -> &'static str {
+        "tikz"
+    }
+
+    fn extension(&self) -> &'static str {
+        "tex"
+    }
+
+    fn mime_type(&self) -> &'static str {
+        "application/x-tex"
+    }
+
+    fn capabilities(&self) -> TerminalCapabilities {
+        TerminalCapabilities {
+            stdout: true,
+            text: true,
+            vector: true,
+            latex: true,
+            ..Default::default()
+        }
+    }
+
+    fn terminal_output(&self) -> &Output {
+        &self.output
+    }
+
+    fn terminal_output_mut(&mut self) -> &mut Output {
+        &mut self.output
+    }
+
+    fn term_commands(&self) -> GnuCommandFactoryResult {
+        let options = [
+            "latex".to_string(),
+            self.color.to_string(),
+            self.document.to_string(),
+            self.header.to_string(),
+            self.size.to_string()
+        ];
+        let options = join_options(&options);
+
+        let mut commands = VecDeque::new();
+        commands.push_back(
+            GnuCommand::new(format!("set term tikz {}", options))
+        );
+
+        Ok(commands)
+    }
+}
+
+impl GnuCommandFactory for Tikz {
+    fn as_commands(&self) -> GnuCommandFactoryResult {
+        terminal_as_commands(self, "Tikz")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cairo_latex_terminal_creation() {
+        let mut terminal = CairoLatex::default();
+        terminal.graphics().update(CairoLatexGraphics::Eps);
+        terminal.document().update(LatexDocument::Standalone);
+        terminal.header().update("\\usepackage{amsmath}");
+        terminal.size().centimetres(12.0, 8.0);
+        terminal.output().update("./figure.tex");
+        let commands = terminal.as_commands().unwrap();
+
+        assert_eq!(commands.len(), 2);
+        assert_eq!(commands[0].to_string(), "set output './figure.tex'");
+        assert_eq!(commands[1].to_string(), "set term cairolatex eps standalone header '\\usepackage{amsmath}' size 12cm,8cm");
+    }
+
+    #[test]
+    fn test_cairo_latex_terminal_empty_creation() {
+        let mut terminal = CairoLatex::default();
+        terminal.output().update("./figure.tex");
+        let commands = terminal.as_commands().unwrap();
+
+        assert_eq!(commands[1].to_string(), "set term cairolatex pdf input");
+    }
+
+    #[test]
+    fn test_tikz_terminal_creation() {
+        let mut terminal = Tikz::default();
+        terminal.color().update(TikzColor::Monochrome);
+        terminal.document().update(LatexDocument::Standalone);
+        terminal.header().update("\\usepackage{amsmath}");
+        terminal.output().update("./figure.tex");
+        let commands = terminal.as_commands().unwrap();
+
+        assert_eq!(commands.len(), 2);
+        assert_eq!(commands[0].to_string(), "set output './figure.tex'");
+        assert_eq!(commands[1].to_string(), "set term tikz latex monochrome standalone header '\\usepackage{amsmath}'");
+    }
+
+    #[test]
+    fn test_latex_terminal_creation_without_filename_fails() {
+        assert!(CairoLatex::default().as_commands().is_err());
+        assert!(Tikz::default().as_commands().is_err());
+    }
+}
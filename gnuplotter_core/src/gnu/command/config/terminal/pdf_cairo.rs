@@ -0,0 +1,188 @@
+use std::collections::VecDeque;
+use std::fmt::{Display, Formatter};
+use crate::prelude::*;
+use crate::prelude::prelude::GnuCommandFactoryResult;
+use super::{Font, Output, PageSize, Terminal, TerminalCapabilities, join_options, terminal_as_commands};
+
+/// Scales the width of all lines in the plot
+#[derive(Debug, Clone, PartialEq, Default)]
+pub enum PdfCairoLineWidth {
+    #[default]
+    Default,
+    Value(f64)
+}
+
+impl PdfCairoLineWidth {
+    pub fn update(&mut self, line_width: f64) {
+        *self = PdfCairoLineWidth::Value(line_width);
+    }
+}
+
+impl Display for PdfCairoLineWidth {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PdfCairoLineWidth::Default => f.write_str(""),
+            PdfCairoLineWidth::Value(line_width) => {
+                f.write_fmt(
+                    format_args!("linewidth {}", line_width)
+                )
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub enum PdfCairoColor {
+    #[default]
+    Default,
+    Color,
+    Monochrome
+}
+
+impl PdfCairoColor {
+    pub fn update(&mut self, color: PdfCairoColor) {
+        *self = color;
+    }
+}
+
+impl Display for PdfCairoColor {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PdfCairoColor::Default => f.write_str(""),
+            PdfCairoColor::Color => f.write_str("color"),
+            PdfCairoColor::Monochrome => f.write_str("mono")
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct PdfCairo {
+    size: PageSize,
+    font: Font,
+    line_width: PdfCairoLineWidth,
+    color: PdfCairoColor,
+    output: Output
+}
+
+impl PdfCairo {
+    pub fn size(&mut self) -> &mut PageSize {
+        &mut self.size
+    }
+
+    pub fn font(&mut self) -> &mut Font {
+        &mut self.font
+    }
+
+    pub fn line_width(&mut self) -> &mut PdfCairoLineWidth {
+        &mut self.line_width
+    }
+
+    pub fn color(&mut self) -> &mut PdfCairoColor {
+        &mut self.color
+    }
+
+    pub fn output(&mut self) -> &mut Output {
+        &mut self.output
+    }
+}
+
+impl Terminal for PdfCairo {
+    fn name(&self) -> &'static str {
+        "pdfcairo"
+    }
+
+    fn extension(&self) -> &'static str {
+        "pdf"
+    }
+
+    fn mime_type(&self) -> &'static str {
+        "application/pdf"
+    }
+
+    fn capabilities(&self) -> TerminalCapabilities {
+        TerminalCapabilities {
+            stdout: true,
+            vector: true,
+            ..Default::default()
+        }
+    }
+
+    fn terminal_output(&self) -> &Output {
+        &self.output
+    }
+
+    fn terminal_output_mut(&mut self) -> &mut Output {
+        &mut self.output
+    }
+
+    fn term_commands(&self) -> GnuCommandFactoryResult {
+        let options = [
+            self.color.to_string(),
+            self.size.to_string(),
+            self.font.to_string(),
+            self.line_width.to_string()
+        ];
+        let options = join_options(&options);
+
+        let mut commands = VecDeque::new();
+        commands.push_back(
+            GnuCommand::new(format!("set term pdfcairo enhanced {}", options).trim())
+        );
+
+        Ok(commands)
+    }
+}
+
+impl GnuCommandFactory for PdfCairo {
+    fn as_commands(&self) -> GnuCommandFactoryResult {
+        terminal_as_commands(self, "PDFCairo")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pdf_cairo_terminal_creation() {
+        let mut terminal = PdfCairo::default();
+        terminal.size().inches(5.0, 3.5);
+        terminal.font().update("Helvetica", 9);
+        terminal.line_width().update(1.5);
+        terminal.color().update(PdfCairoColor::Monochrome);
+        terminal.output().update("./result.pdf");
+        let commands = terminal.as_commands().unwrap();
+
+        assert_eq!(commands.len(), 2);
+        assert_eq!(commands[0].to_string(), "set output './result.pdf'");
+        assert_eq!(commands[1].to_string(), "set term pdfcairo enhanced mono size 5in,3.5in font \"Helvetica,9\" linewidth 1.5");
+    }
+
+    #[test]
+    fn test_pdf_cairo_terminal_size_in_centimetres() {
+        let mut terminal = PdfCairo::default();
+        terminal.size().centimetres(12.0, 8.0);
+        terminal.output().update("./result.pdf");
+        let commands = terminal.as_commands().unwrap();
+
+        assert_eq!(commands[1].to_string(), "set term pdfcairo enhanced size 12cm,8cm");
+    }
+
+    #[test]
+    fn test_pdf_cairo_terminal_empty_creation() {
+        let mut terminal = PdfCairo::default();
+        terminal.output().update("./result.pdf");
+        let commands = terminal.as_commands().unwrap();
+
+        assert_eq!(commands.len(), 2);
+        assert_eq!(commands[0].to_string(), "set output './result.pdf'");
+        assert_eq!(commands[1].to_string(), "set term pdfcairo enhanced");
+    }
+
+    #[test]
+    fn test_pdf_cairo_terminal_creation_without_filename_fails() {
+        let terminal = PdfCairo::default();
+
+        assert!(terminal.as_commands().is_err());
+    }
+}
@@ -0,0 +1,214 @@
+use std::collections::VecDeque;
+use std::fmt::{Display, Formatter};
+use crate::prelude::*;
+use crate::prelude::prelude::GnuCommandFactoryResult;
+use super::{Font, Output, PageSize, Terminal, TerminalCapabilities, join_options, terminal_as_commands};
+
+/// Selects between a full postscript page and an encapsulated postscript figure (`Eps`), which is
+/// what most journals expect.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub enum PostScriptMode {
+    #[default]
+    Default,
+    Eps,
+    Landscape,
+    Portrait
+}
+
+impl PostScriptMode {
+    pub fn update(&mut self, mode: PostScriptMode) {
+        *self = mode;
+    }
+}
+
+impl Display for PostScriptMode {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PostScriptMode::Default => f.write_str(""),
+            PostScriptMode::Eps => f.write_str("eps"),
+            PostScriptMode::Landscape => f.write_str("landscape"),
+            PostScriptMode::Portrait => f.write_str("portrait")
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub enum PostScriptColor {
+    #[default]
+    Default,
+    Color,
+    Monochrome
+}
+
+impl PostScriptColor {
+    pub fn update(&mut self, color: PostScriptColor) {
+        *self = color;
+    }
+}
+
+impl Display for PostScriptColor {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PostScriptColor::Default => f.write_str(""),
+            PostScriptColor::Color => f.write_str("color"),
+            PostScriptColor::Monochrome => f.write_str("monochrome")
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub enum PostScriptDash {
+    #[default]
+    Default,
+    Solid,
+    Dashed
+}
+
+impl PostScriptDash {
+    pub fn update(&mut self, dash: PostScriptDash) {
+        *self = dash;
+    }
+}
+
+impl Display for PostScriptDash {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PostScriptDash::Default => f.write_str(""),
+            PostScriptDash::Solid => f.write_str("solid"),
+            PostScriptDash::Dashed => f.write_str("dashed")
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct PostScript {
+    mode: PostScriptMode,
+    color: PostScriptColor,
+    dash: PostScriptDash,
+    size: PageSize,
+    font: Font,
+    output: Output
+}
+
+impl PostScript {
+    pub fn mode(&mut self) -> &mut PostScriptMode {
+        &mut self.mode
+    }
+
+    pub fn color(&mut self) -> &mut PostScriptColor {
+        &mut self.color
+    }
+
+    pub fn dash(&mut self) -> &mut PostScriptDash {
+        &mut self.dash
+    }
+
+    pub fn size(&mut self) -> &mut PageSize {
+        &mut self.size
+    }
+
+    pub fn font(&mut self) -> &mut Font {
+        &mut self.font
+    }
+
+    pub fn output(&mut self) -> &mut Output {
+        &mut self.output
+    }
+}
+
+impl Terminal for PostScript {
+    fn name(&self) -> &'static str {
+        "postscript"
+    }
+
+    fn extension(&self) -> &'static str {
+        match self.mode {
+            PostScriptMode::Eps => "eps",
+            _ => "ps"
+        }
+    }
+
+    fn mime_type(&self) -> &'static str {
+        "application/postscript"
+    }
+
+    fn capabilities(&self) -> TerminalCapabilities {
+        TerminalCapabilities {
+            stdout: true,
+            vector: true,
+            ..Default::default()
+        }
+    }
+
+    fn terminal_output(&self) -> &Output {
+        &self.output
+    }
+
+    fn terminal_output_mut(&mut self) -> &mut Output {
+        &mut self.output
+    }
+
+    fn term_commands(&self) -> GnuCommandFactoryResult {
+        let options = [
+            self.mode.to_string(),
+            "enhanced".to_string(),
+            self.color.to_string(),
+            self.dash.to_string(),
+            self.font.to_string(),
+            self.size.to_string()
+        ];
+        let options = join_options(&options);
+
+        let mut commands = VecDeque::new();
+        commands.push_back(
+            GnuCommand::new(format!("set term postscript {}", options))
+        );
+
+        Ok(commands)
+    }
+}
+
+impl GnuCommandFactory for PostScript {
+    fn as_commands(&self) -> GnuCommandFactoryResult {
+        terminal_as_commands(self, "PostScript")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_post_script_terminal_creation() {
+        let mut terminal = PostScript::default();
+        terminal.mode().update(PostScriptMode::Eps);
+        terminal.color().update(PostScriptColor::Color);
+        terminal.dash().update(PostScriptDash::Dashed);
+        terminal.font().update("Times-Roman", 12);
+        terminal.size().inches(3.5, 2.5);
+        terminal.output().update("./result.eps");
+        let commands = terminal.as_commands().unwrap();
+
+        assert_eq!(commands.len(), 2);
+        assert_eq!(commands[0].to_string(), "set output './result.eps'");
+        assert_eq!(commands[1].to_string(), "set term postscript eps enhanced color dashed font \"Times-Roman,12\" size 3.5in,2.5in");
+    }
+
+    #[test]
+    fn test_post_script_terminal_empty_creation() {
+        let mut terminal = PostScript::default();
+        terminal.output().update("./result.ps");
+        let commands = terminal.as_commands().unwrap();
+
+        assert_eq!(commands.len(), 2);
+        assert_eq!(commands[0].to_string(), "set output './result.ps'");
+        assert_eq!(commands[1].to_string(), "set term postscript enhanced");
+    }
+
+    #[test]
+    fn test_post_script_terminal_creation_without_filename_fails() {
+        let terminal = PostScript::default();
+
+        assert!(terminal.as_commands().is_err());
+    }
+}
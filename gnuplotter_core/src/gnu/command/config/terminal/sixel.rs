@@ -0,0 +1,152 @@
+use std::collections::VecDeque;
+use std::fmt::{Display, Formatter};
+use crate::prelude::*;
+use crate::prelude::prelude::GnuCommandFactoryResult;
+use super::{Font, Output, Size, Terminal, TerminalCapabilities, join_options, terminal_as_commands};
+
+/// Uses 24 bit colors instead of a palette. Not every terminal emulator supports these.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub enum SixelColor {
+    #[default]
+    Default,
+    Palette,
+    TrueColor
+}
+
+impl SixelColor {
+    pub fn update(&mut self, color: SixelColor) {
+        *self = color;
+    }
+}
+
+impl Display for SixelColor {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SixelColor::Default => f.write_str(""),
+            SixelColor::Palette => f.write_str("notruecolor"),
+            SixelColor::TrueColor => f.write_str("truecolor")
+        }
+    }
+}
+
+/// Renders the plot as sixel graphics using gnuplot's `sixelgd` terminal, which requires gnuplot
+/// 5.2 or newer. The escape sequences are written to STDOUT unless a file is given. On STDOUT, they
+/// show the plot inline in a sixel capable terminal emulator, or can be captured using
+/// `CanRender::render_to_bytes()`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Sixel {
+    size: Size,
+    font: Font,
+    color: SixelColor,
+    output: Output
+}
+
+impl Default for Sixel {
+    fn default() -> Self {
+        Sixel {
+            size: Size::default(),
+            font: Font::default(),
+            color: SixelColor::default(),
+            output: Output::Stdout
+        }
+    }
+}
+
+impl Sixel {
+    pub fn size(&mut self) -> &mut Size {
+        &mut self.size
+    }
+
+    pub fn font(&mut self) -> &mut Font {
+        &mut self.font
+    }
+
+    pub fn color(&mut self) -> &mut SixelColor {
+        &mut self.color
+    }
+
+    pub fn output(&mut self) -> &mut Output {
+        &mut self.output
+    }
+}
+
+impl Terminal for Sixel {
+    fn name(&self) -> &'static str {
+        "sixelgd"
+    }
+
+    fn extension(&self) -> &'static str {
+        "six"
+    }
+
+    fn mime_type(&self) -> &'static str {
+        "image/x-sixel"
+    }
+
+    fn capabilities(&self) -> TerminalCapabilities {
+        TerminalCapabilities {
+            stdout: true,
+            ..Default::default()
+        }
+    }
+
+    fn terminal_output(&self) -> &Output {
+        &self.output
+    }
+
+    fn terminal_output_mut(&mut self) -> &mut Output {
+        &mut self.output
+    }
+
+    fn term_commands(&self) -> GnuCommandFactoryResult {
+        let options = [
+            self.color.to_string(),
+            self.size.to_string(),
+            self.font.to_string()
+        ];
+        let options = join_options(&options);
+
+        let mut commands = VecDeque::new();
+        commands.push_back(
+            GnuCommand::new(format!("set term sixelgd enhanced {}", options).trim())
+                .requires(Version::new(5, 2, 0))
+        );
+
+        Ok(commands)
+    }
+}
+
+impl GnuCommandFactory for Sixel {
+    fn as_commands(&self) -> GnuCommandFactoryResult {
+        terminal_as_commands(self, "Sixel")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sixel_terminal_creation() {
+        let mut terminal = Sixel::default();
+        terminal.size().update(640, 480);
+        terminal.font().update("Helvetica", 10);
+        terminal.color().update(SixelColor::TrueColor);
+        let commands = terminal.as_commands().unwrap();
+
+        assert_eq!(commands.len(), 1);
+        assert_eq!(commands[0].to_string(), "set term sixelgd enhanced truecolor size 640,480 font \"Helvetica,10\"");
+        assert_eq!(commands[0].required_version(), Some(Version::new(5, 2, 0)));
+    }
+
+    #[test]
+    fn test_sixel_terminal_creation_with_filename() {
+        let mut terminal = Sixel::default();
+        terminal.output().update("./result.six");
+        let commands = terminal.as_commands().unwrap();
+
+        assert_eq!(commands.len(), 2);
+        assert_eq!(commands[0].to_string(), "set output './result.six'");
+        assert_eq!(commands[1].to_string(), "set term sixelgd enhanced");
+    }
+}
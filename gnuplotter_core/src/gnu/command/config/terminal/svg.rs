@@ -0,0 +1,213 @@
+use std::collections::VecDeque;
+use std::fmt::{Display, Formatter};
+use crate::prelude::*;
+use crate::prelude::prelude::GnuCommandFactoryResult;
+use super::{Font, Output, Size, Terminal, TerminalCapabilities, join_options, terminal_as_commands};
+
+/// Decides whether a browser may rescale the plot (`dynamic`) or must keep the size it was
+/// rendered at (`fixed`). Gnuplot's own default is used when left untouched.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub enum SvgSizing {
+    #[default]
+    Default,
+    Fixed,
+    Dynamic
+}
+
+impl SvgSizing {
+    pub fn update(&mut self, sizing: SvgSizing) {
+        *self = sizing;
+    }
+}
+
+impl Display for SvgSizing {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SvgSizing::Default => f.write_str(""),
+            SvgSizing::Fixed => f.write_str("fixed"),
+            SvgSizing::Dynamic => f.write_str("dynamic")
+        }
+    }
+}
+
+/// Adds javascript mouse tracking to the svg. A `Standalone` svg embeds the javascript, so it
+/// keeps working without access to gnuplot's javascript directory.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub enum SvgMouse {
+    #[default]
+    Disabled,
+    Enabled,
+    Standalone
+}
+
+impl SvgMouse {
+    pub fn update(&mut self, mouse: SvgMouse) {
+        *self = mouse;
+    }
+}
+
+impl Display for SvgMouse {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SvgMouse::Disabled => f.write_str(""),
+            SvgMouse::Enabled => f.write_str("mouse"),
+            SvgMouse::Standalone => f.write_str("mouse standalone")
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub enum SvgBackground {
+    #[default]
+    Default,
+    Color(String)
+}
+
+impl SvgBackground {
+    pub fn update(&mut self, color: &str) {
+        *self = SvgBackground::Color(color.into());
+    }
+}
+
+impl Display for SvgBackground {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SvgBackground::Default => f.write_str(""),
+            SvgBackground::Color(color) => {
+                f.write_fmt(
+                    format_args!("background \"{}\"", color)
+                )
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Svg {
+    size: Size,
+    sizing: SvgSizing,
+    font: Font,
+    background: SvgBackground,
+    mouse: SvgMouse,
+    output: Output
+}
+
+impl Svg {
+    pub fn size(&mut self) -> &mut Size {
+        &mut self.size
+    }
+
+    pub fn sizing(&mut self) -> &mut SvgSizing {
+        &mut self.sizing
+    }
+
+    pub fn font(&mut self) -> &mut Font {
+        &mut self.font
+    }
+
+    pub fn background(&mut self) -> &mut SvgBackground {
+        &mut self.background
+    }
+
+    pub fn mouse(&mut self) -> &mut SvgMouse {
+        &mut self.mouse
+    }
+
+    pub fn output(&mut self) -> &mut Output {
+        &mut self.output
+    }
+}
+
+impl Terminal for Svg {
+    fn name(&self) -> &'static str {
+        "svg"
+    }
+
+    fn extension(&self) -> &'static str {
+        "svg"
+    }
+
+    fn mime_type(&self) -> &'static str {
+        "image/svg+xml"
+    }
+
+    fn capabilities(&self) -> TerminalCapabilities {
+        TerminalCapabilities {
+            stdout: true,
+            vector: true,
+            interactive: true,
+            ..Default::default()
+        }
+    }
+
+    fn terminal_output(&self) -> &Output {
+        &self.output
+    }
+
+    fn terminal_output_mut(&mut self) -> &mut Output {
+        &mut self.output
+    }
+
+    fn term_commands(&self) -> GnuCommandFactoryResult {
+        let options = [
+            self.size.to_string(),
+            self.sizing.to_string(),
+            self.font.to_string(),
+            self.background.to_string(),
+            self.mouse.to_string()
+        ];
+        let options = join_options(&options);
+
+        let mut commands = VecDeque::new();
+        commands.push_back(
+            GnuCommand::new(format!("set term svg enhanced {}", options).trim())
+        );
+
+        Ok(commands)
+    }
+}
+
+impl GnuCommandFactory for Svg {
+    fn as_commands(&self) -> GnuCommandFactoryResult {
+        terminal_as_commands(self, "SVG")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_svg_terminal_creation() {
+        let mut terminal = Svg::default();
+        terminal.size().update(1200, 800);
+        terminal.sizing().update(SvgSizing::Dynamic);
+        terminal.font().update("Helvetica", 14);
+        terminal.background().update("#ffffff");
+        terminal.mouse().update(SvgMouse::Standalone);
+        terminal.output().update("./result.svg");
+        let commands = terminal.as_commands().unwrap();
+
+        assert_eq!(commands.len(), 2);
+        assert_eq!(commands[0].to_string(), "set output './result.svg'");
+        assert_eq!(commands[1].to_string(), "set term svg enhanced size 1200,800 dynamic font \"Helvetica,14\" background \"#ffffff\" mouse standalone");
+    }
+
+    #[test]
+    fn test_svg_terminal_empty_creation() {
+        let mut terminal = Svg::default();
+        terminal.output().update("./result.svg");
+        let commands = terminal.as_commands().unwrap();
+
+        assert_eq!(commands.len(), 2);
+        assert_eq!(commands[0].to_string(), "set output './result.svg'");
+        assert_eq!(commands[1].to_string(), "set term svg enhanced");
+    }
+
+    #[test]
+    fn test_svg_terminal_creation_without_filename_fails() {
+        let terminal = Svg::default();
+
+        assert!(terminal.as_commands().is_err());
+    }
+}
@@ -0,0 +1,36 @@
+//! Helpers for the text of titles and labels.
+
+/// Detects LaTeX math in a text, e.g. `Energy $E = mc^2$`.
+pub(crate) fn contains_latex_math(text: &str) -> bool {
+    let mut delimiters = text.match_indices('$').map(|(index, _)| index);
+    match (delimiters.next(), delimiters.next()) {
+        (Some(start), Some(end)) => end > start + 1,
+        _ => false
+    }
+}
+
+/// Quotes text containing LaTeX math so it reaches the `CairoLatex` and `Tikz` terminals verbatim.
+/// Single quotes stop gnuplot from interpreting backslashes, while `noenhanced` keeps it from
+/// reading `^` and `_` as super- and subscripts. Other terminals get the text as before.
+pub(crate) fn latex_math_string(text: &str) -> String {
+    format!("'{}' noenhanced", text.replace('\'', "''"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_latex_math_is_detected() {
+        assert!(contains_latex_math("$\\alpha$"));
+        assert!(contains_latex_math("Energy $E = mc^2$ in J"));
+        assert!(!contains_latex_math("Costs in $"));
+        assert!(!contains_latex_math("$$"));
+        assert!(!contains_latex_math("plain text"));
+    }
+
+    #[test]
+    fn test_latex_math_is_quoted_verbatim() {
+        assert_eq!(latex_math_string("$\\alpha$ isn't $\\beta$"), "'$\\alpha$ isn''t $\\beta$' noenhanced");
+    }
+}
@@ -1,4 +1,5 @@
 use crate::gnu::command::*;
+use crate::gnu::command::text::{contains_latex_math, latex_math_string};
 use crate::maybe::Maybe;
 use crate::required::Required;
 
@@ -13,7 +14,12 @@ impl Title {
 
 impl GnuCommandFactory for Title {
     fn as_commands(&self) -> GnuCommandFactoryResult {
-        Ok(vec![GnuCommand(format!("set title '{}'", self.0))].into())
+        let mut command = GnuCommand::new(format!("set title '{}'", self.0));
+        if contains_latex_math(&self.0) {
+            command = command.with_latex(format!("set title {}", latex_math_string(&self.0)));
+        }
+
+        Ok(vec![command].into())
     }
 }
 
@@ -44,4 +50,34 @@ mod tests {
         let maybe_title: Required<Title> = "an experiment".into();
         assert_eq!(maybe_title, Required::value(Title::new("an experiment")));
     }
+
+    #[test]
+    fn test_a_title_has_title_information() {
+        let title = Title::new("an experiment");
+        let commands = title.as_commands().unwrap();
+
+        assert_eq!(commands[0].to_string(), "set title 'an experiment'");
+    }
+
+    #[test]
+    fn test_a_title_with_latex_math_is_passed_verbatim_to_latex_terminals() {
+        let mut terminal = CairoLatex::default();
+        terminal.output().update("./figure.tex");
+        let mut commands = Title::new("Decay of $\\lambda_i$").as_commands().unwrap();
+        commands.append(&mut Config::new(terminal).as_commands().unwrap());
+        let commands = GnuCommand::typeset(commands);
+
+        assert_eq!(commands[0].to_string(), "set title 'Decay of $\\lambda_i$' noenhanced");
+    }
+
+    #[test]
+    fn test_a_title_with_dollar_signs_is_unchanged_for_other_terminals() {
+        let mut terminal = PngCairo::default();
+        terminal.output().update("./figure.png");
+        let mut commands = Title::new("Costs $5 and $10").as_commands().unwrap();
+        commands.append(&mut Config::new(terminal).as_commands().unwrap());
+        let commands = GnuCommand::typeset(commands);
+
+        assert_eq!(commands[0].to_string(), "set title 'Costs $5 and $10'");
+    }
 }
\ No newline at end of file
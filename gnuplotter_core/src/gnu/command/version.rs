@@ -0,0 +1,82 @@
+use std::collections::VecDeque;
+use std::fmt::{Display, Formatter};
+use crate::prelude::*;
+
+/// A gnuplot version, such as `5.4 patchlevel 2`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Version {
+    pub major: u32,
+    pub minor: u32,
+    pub patchlevel: u32
+}
+
+impl Version {
+    pub const fn new(major: u32, minor: u32, patchlevel: u32) -> Self {
+        Version { major, minor, patchlevel }
+    }
+
+    /// Reads the version printed by `gnuplot --version`, e.g. `gnuplot 5.4 patchlevel 2`. A
+    /// patchlevel that is not a number, such as `rc1`, is read as 0.
+    pub fn parse(output: &str) -> Option<Version> {
+        let mut words = output.split_whitespace().skip_while(|word| *word != "gnuplot").skip(1);
+
+        let (major, minor) = words.next()?.split_once('.')?;
+        let patchlevel = match (words.next(), words.next()) {
+            (Some("patchlevel"), Some(patchlevel)) => patchlevel.parse().unwrap_or(0),
+            _ => 0
+        };
+
+        Some(Version {
+            major: major.parse().ok()?,
+            minor: minor.parse().ok()?,
+            patchlevel
+        })
+    }
+
+    /// Fails on the first command requiring a newer version of gnuplot
+    pub fn check(&self, commands: &VecDeque<GnuCommand>) -> std::result::Result<(), GnuCommandFactoryError> {
+        for command in commands {
+            match command.required_version() {
+                Some(required) if required > *self => {
+                    return Err(GnuCommandFactoryError::message(&format!(
+                        "`{}` requires gnuplot {} or newer, but gnuplot {} is used",
+                        command, required, self
+                    )));
+                },
+                _ => {}
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl Display for Version {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_fmt(format_args!("{}.{} patchlevel {}", self.major, self.minor, self.patchlevel))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_version_is_parsed() {
+        assert_eq!(Version::parse("gnuplot 5.4 patchlevel 2\n"), Some(Version::new(5, 4, 2)));
+        assert_eq!(Version::parse("gnuplot 6.0 patchlevel rc1"), Some(Version::new(6, 0, 0)));
+        assert_eq!(Version::parse("something else"), None);
+    }
+
+    #[test]
+    fn test_commands_requiring_a_newer_version_fail() {
+        let commands: VecDeque<GnuCommand> = vec![
+            GnuCommand::new("set title 'versions'"),
+            GnuCommand::new("set term sixelgd").requires(Version::new(5, 2, 0)),
+        ].into();
+
+        assert!(Version::new(5, 2, 0).check(&commands).is_ok());
+        assert!(Version::new(6, 0, 0).check(&commands).is_ok());
+        assert!(Version::new(5, 0, 6).check(&commands).is_err());
+    }
+}
@@ -1,3 +1,6 @@
 pub mod source;
 pub mod serie;
-pub mod series;
\ No newline at end of file
+pub mod series;
+pub mod workspace;
+pub mod xy_serie;
+pub mod xy_series;
\ No newline at end of file
@@ -1,7 +1,8 @@
 use std::collections::VecDeque;
 use std::fs;
 use std::fs::File;
-use std::io::Write;
+use std::io::{BufWriter, Write};
+use std::sync::Arc;
 use rand::random;
 use crate::prelude::*;
 
@@ -17,12 +18,181 @@ impl SeriesError {
 
 pub type SeriesResult = std::result::Result<String, SeriesError>;
 
+/// Written in place of the values of a serie that ended before the others. Gnuplot does not plot
+/// points without a number, so shorter series simply end early.
+pub const MISSING_VALUE: &str = "NaN";
+
+/// Decides where gnuplot reads the data of a series from
+#[derive(Debug, Clone, PartialEq, Default)]
+pub enum SeriesStorage {
+    /// The data is embedded in the commands as a `$DATA` datablock, so rendering needs no
+    /// writable working directory. Requires gnuplot 5.0 or newer.
+    #[default]
+    Inline,
+    /// The data is written to the file, which gnuplot reads while plotting
+    File(String),
+    /// The data is written to a uniquely named file in the workspace, which is removed once
+    /// gnuplot finished plotting
+    Workspace(DataWorkspace)
+}
+
+impl SeriesStorage {
+    pub fn update(&mut self, storage: SeriesStorage) {
+        *self = storage;
+    }
+
+    /// Stores the rows where gnuplot reads them from, followed by the plot command generated from
+    /// the source gnuplot reads the rows from
+    pub(crate) fn commands<F>(&self, rows: &[String], plot: F) -> GnuCommandFactoryResult
+    where
+        F: Fn(&str) -> std::result::Result<String, GnuCommandFactoryError>
+    {
+        if *self == SeriesStorage::Inline {
+            return Ok(vec![datablock(rows), GnuCommand::new(plot("$DATA")?)].into());
+        }
+
+        let (path, data_file) = self.store(&|file| {
+            for row in rows {
+                writeln!(file, "{}", row)?;
+            }
+            Ok(())
+        })?;
+        let command = GnuCommand::new(plot(&format!("\"{}\"", path))?).reads(&path);
+
+        Ok(vec![reading(command, data_file)].into())
+    }
+
+    /// Stores the binary records of float64 values written by `write`, each holding `columns`
+    /// values, and generates the plot command like `commands` does. Binary data can't be inlined,
+    /// so inlined data is written to the default `DataWorkspace` instead.
+    pub(crate) fn binary_commands<F>(&self, columns: usize, write: WriteData, plot: F) -> GnuCommandFactoryResult
+    where
+        F: Fn(&str) -> std::result::Result<String, GnuCommandFactoryError>
+    {
+        let storage = match self {
+            SeriesStorage::Inline => SeriesStorage::Workspace(DataWorkspace::default()),
+            storage => storage.clone()
+        };
+        let (path, data_file) = storage.store(write)?;
+        let source = format!("\"{}\" binary format=\"{}\" endian=little", path, "%float64".repeat(columns));
+        let command = GnuCommand::new(plot(&source)?).reads_binary(&path);
+
+        Ok(vec![reading(command, data_file)].into())
+    }
+
+    /// Writes the data to a file, returning its path along with the file when it is to be removed
+    /// after rendering
+    fn store(&self, write: WriteData) -> std::result::Result<(String, Option<DataFile>), GnuCommandFactoryError> {
+        let write_error = || GnuCommandFactoryError::io_error("Unable to write series data");
+
+        match self {
+            SeriesStorage::Inline => Err(
+                GnuCommandFactoryError::message("Inlined series data is not stored in a file")
+            ),
+            SeriesStorage::File(filename) => {
+                if let Some(directory) = std::path::Path::new(filename).parent() {
+                    let _ = fs::create_dir_all(directory);
+                }
+                let file = File::create(filename).or(Err(write_error()))?;
+                write_buffered(file, write).or(Err(write_error()))?;
+
+                Ok((filename.clone(), None))
+            },
+            SeriesStorage::Workspace(workspace) => {
+                let (data_file, file) = workspace.allocate("series")?;
+                write_buffered(file, write).or(Err(write_error()))?;
+
+                Ok((data_file.path().display().to_string(), Some(data_file)))
+            }
+        }
+    }
+}
+
+/// Writes the data of a series into the file it is stored in
+pub(crate) type WriteData<'a> = &'a dyn Fn(&mut dyn Write) -> std::io::Result<()>;
+
+/// Writes the data through a buffer, so large series are written in large chunks
+fn write_buffered(file: File, write: WriteData) -> std::io::Result<()> {
+    let mut file = BufWriter::new(file);
+    write(&mut file)?;
+    file.flush()
+}
+
+fn reading(command: GnuCommand, data_file: Option<DataFile>) -> GnuCommand {
+    match data_file {
+        Some(data_file) => command.reading(Arc::new(data_file)),
+        None => command
+    }
+}
+
+/// Defines the `$DATA` datablock holding every row
+fn datablock(rows: &[String]) -> GnuCommand {
+    let mut definition = "$DATA << EOD\n".to_string();
+    for row in rows {
+        definition += row;
+        definition += "\n";
+    }
+    definition += "EOD";
+
+    GnuCommand::new(definition).requires(Version::new(5, 0, 0))
+}
+
+fn write_rows(mut file: File, rows: &[String]) -> std::result::Result<(), SeriesError> {
+    for row in rows {
+        if writeln!(file, "{}", row).is_err() {
+            return Err(
+                SeriesError::io_error("Unable to write series data")
+            )
+        }
+    }
+
+    Ok(())
+}
+
+/// Decides how the values of a series are written for gnuplot
+#[derive(Debug, Clone, Copy, Default)]
+pub enum SeriesFormat<T> {
+    /// Values are written as text, in the format of their `to_string()`
+    #[default]
+    Text,
+    /// Values are converted to float64 and written as binary records, which is considerably faster
+    /// for very large series. Missing values are written as NaN.
+    Float64(fn(&T) -> f64)
+}
+
+impl<T> SeriesFormat<T>
+where
+    T: Copy + Into<f64>
+{
+    pub fn float64() -> Self {
+        SeriesFormat::Float64(|value| (*value).into())
+    }
+}
+
+impl<T> SeriesFormat<T> {
+    pub fn update(&mut self, format: SeriesFormat<T>) {
+        *self = format;
+    }
+}
+
+/// Formats are equal when they write the same kind of data
+impl<T> PartialEq for SeriesFormat<T> {
+    fn eq(&self, other: &Self) -> bool {
+        matches!(
+            (self, other),
+            (SeriesFormat::Text, SeriesFormat::Text) | (SeriesFormat::Float64(_), SeriesFormat::Float64(_))
+        )
+    }
+}
+
 #[derive(Default, PartialEq, Debug, Clone)]
 pub struct Series<T>
 where
     T: Default + Clone + ToString
 {
-    data: Vec<Serie<T>>
+    data: Vec<Serie<T>>,
+    storage: SeriesStorage,
+    format: SeriesFormat<T>
 }
 
 impl<T> Series<T>
@@ -50,6 +220,14 @@ where
         self.len() == 0
     }
 
+    pub fn storage(&mut self) -> &mut SeriesStorage {
+        &mut self.storage
+    }
+
+    pub fn format(&mut self) -> &mut SeriesFormat<T> {
+        &mut self.format
+    }
+
     pub fn iter(&self) -> SeriesIterator<T> {
         SeriesIterator {
             data: self.clone(),
@@ -59,16 +237,8 @@ where
 
     pub fn write_to_file(&self, filename: &str) -> SeriesResult {
         match File::create(filename) {
-            Ok(mut file) => {
-
-                for (index, values) in self.iter() {
-                    let data = values.iter().map(|x| x.to_string()).collect::<Vec<String>>().join("\t");
-                    if let Err(_) = writeln!(file, "{}\t{}", index, data) {
-                        return Err(
-                            SeriesError::io_error("Unable to write series data".into())
-                        )
-                    }
-                }
+            Ok(file) => {
+                write_rows(file, &self.rows())?;
 
                 Ok(filename.into())
             },
@@ -77,6 +247,37 @@ where
             )
         }
     }
+
+    /// The data as tab separated rows, starting with the index of the row. Values missing from
+    /// shorter series are written as `MISSING_VALUE`.
+    fn rows(&self) -> Vec<String> {
+        self.iter()
+            .map(|(index, values)| {
+                let data = values
+                    .iter()
+                    .map(|x| x.as_ref().map_or(MISSING_VALUE.into(), T::to_string))
+                    .collect::<Vec<String>>()
+                    .join("\t");
+                format!("{}\t{}", index, data)
+            })
+            .collect()
+    }
+
+    /// Writes the data as binary records like `rows`, with every value converted to a little
+    /// endian float64. Values are read from the series in place, as they may count in the tens of
+    /// millions.
+    fn write_binary_records(&self, file: &mut dyn Write, encode: fn(&T) -> f64) -> std::io::Result<()> {
+        let mut series: Vec<std::slice::Iter<'_, T>> = self.data.iter().map(Serie::iter).collect();
+        for index in 0..self.len() {
+            file.write_all(&(index as f64).to_le_bytes())?;
+            for serie in series.iter_mut() {
+                let value = serie.next().map_or(f64::NAN, encode);
+                file.write_all(&value.to_le_bytes())?;
+            }
+        }
+
+        Ok(())
+    }
 }
 
 #[derive(Default, PartialEq, Debug, Clone)]
@@ -92,7 +293,9 @@ impl<T> Iterator for SeriesIterator<T>
 where
     T: Default + Clone + ToString
 {
-    type Item = (usize, Vec<T>);
+    /// The index of the row along with the value of every serie, which is `None` once a serie
+    /// shorter than the others ended
+    type Item = (usize, Vec<Option<T>>);
 
     fn next(&mut self) -> Option<Self::Item> {
 
@@ -102,7 +305,7 @@ where
 
         let mut result = vec![];
         for data in self.data.data.iter() {
-            result.push(data.get(self.index).unwrap());
+            result.push(data.get(self.index));
         }
 
         let old_index = self.index;
@@ -126,26 +329,30 @@ where
     T: Default + Clone + ToString
 {
     fn as_commands(&self) -> GnuCommandFactoryResult {
-
-        use std::fs;
-        let _ = fs::create_dir(".tmp");
-
-        let filename = "./.tmp/series_data.txt";
-        self.write_to_file(filename)?;
-
-        let mut command = "plot ".to_string();
-        for i in 0..self.data.len() {
-            if let Some(title) = self.data[i].title() {
-                command += &format!("\"{}\" using 1:{} title '{}' with linespoint, ", filename, i + 2, title);
-            } else {
-                command += &format!("\"{}\" using 1:{} title '' with linespoint, ", filename, i + 2);
+        let plot = |source: &str| {
+            let mut command = "plot ".to_string();
+            for i in 0..self.data.len() {
+                if let Some(title) = self.data[i].title() {
+                    command += &format!("{} using 1:{} title '{}' with linespoint, ", source, i + 2, title);
+                } else {
+                    command += &format!("{} using 1:{} title '' with linespoint, ", source, i + 2);
+                }
             }
-        }
-        let command = command.strip_suffix(", ").ok_or(GnuCommandFactoryError::message("Unable to strip suffix"))?;
 
-        Ok(
-            vec![GnuCommand::new(command)].into()
-        )
+            command
+                .strip_suffix(", ")
+                .map(String::from)
+                .ok_or(GnuCommandFactoryError::message("Unable to strip suffix"))
+        };
+
+        match self.format {
+            SeriesFormat::Text => self.storage.commands(&self.rows(), plot),
+            SeriesFormat::Float64(encode) => self.storage.binary_commands(
+                self.data.len() + 1,
+                &|file| self.write_binary_records(file, encode),
+                plot
+            )
+        }
     }
 }
 
@@ -203,9 +410,9 @@ mod tests {
         series.add(serie_2);
 
         let mut it = series.iter();
-        assert_eq!(it.next(), Some((0, vec![1.0, 2.0])));
-        assert_eq!(it.next(), Some((1, vec![2.0, 4.0])));
-        assert_eq!(it.next(), Some((2, vec![3.0, 6.0])));
+        assert_eq!(it.next(), Some((0, vec![Some(1.0), Some(2.0)])));
+        assert_eq!(it.next(), Some((1, vec![Some(2.0), Some(4.0)])));
+        assert_eq!(it.next(), Some((2, vec![Some(3.0), Some(6.0)])));
     }
 
     #[test]
@@ -223,10 +430,14 @@ mod tests {
         let mut series = Series::new();
         series.add(serie_1);
         series.add(serie_2);
+        let file = std::env::temp_dir().join("gnuplotter_series_command.txt");
+        let filename = file.to_string_lossy().to_string();
+        series.storage().update(SeriesStorage::File(filename.clone()));
 
         let mut command = series.as_commands().unwrap();
 
-        assert_eq!(command.pop_front().unwrap(), GnuCommand::new("plot \"./.tmp/series_data.txt\" using 1:2 title 'A' with linespoint, \"./.tmp/series_data.txt\" using 1:3 title 'B' with linespoint"));
+        assert_eq!(command.pop_front().unwrap(), GnuCommand::new(format!("plot \"{0}\" using 1:2 title 'A' with linespoint, \"{0}\" using 1:3 title 'B' with linespoint", filename)));
+        fs::remove_file(file).unwrap();
     }
 
     #[test]
@@ -244,9 +455,130 @@ mod tests {
         let mut series = Series::new();
         series.add(serie_1);
         series.add(serie_2);
+        let file = std::env::temp_dir().join("gnuplotter_series_command_without_titles.txt");
+        let filename = file.to_string_lossy().to_string();
+        series.storage().update(SeriesStorage::File(filename.clone()));
 
         let mut command = series.as_commands().unwrap();
 
-        assert_eq!(command.pop_front().unwrap(), GnuCommand::new("plot \"./.tmp/series_data.txt\" using 1:2 title '' with linespoint, \"./.tmp/series_data.txt\" using 1:3 title '' with linespoint"));
+        assert_eq!(command.pop_front().unwrap(), GnuCommand::new(format!("plot \"{0}\" using 1:2 title '' with linespoint, \"{0}\" using 1:3 title '' with linespoint", filename)));
+        fs::remove_file(file).unwrap();
+    }
+
+    #[test]
+    fn test_series_data_is_inlined_by_default() {
+        let mut serie_1 = Serie::with_title("A");
+        serie_1.add(1.0);
+        serie_1.add(2.0);
+
+        let mut serie_2 = Serie::with_title("B");
+        serie_2.add(2.0);
+        serie_2.add(4.0);
+
+        let mut series = Series::new();
+        series.add(serie_1);
+        series.add(serie_2);
+
+        let mut commands = series.as_commands().unwrap();
+
+        let datablock = commands.pop_front().unwrap();
+        assert_eq!(datablock.to_string(), "$DATA << EOD\n0\t1\t2\n1\t2\t4\nEOD");
+        assert_eq!(datablock.required_version(), Some(Version::new(5, 0, 0)));
+        assert_eq!(commands.pop_front().unwrap(), GnuCommand::new("plot $DATA using 1:2 title 'A' with linespoint, $DATA using 1:3 title 'B' with linespoint"));
+        assert!(commands.is_empty());
+    }
+
+    #[test]
+    fn test_series_data_is_written_to_the_workspace_until_rendered() {
+        let mut serie = Serie::with_title("A");
+        serie.add(1.0);
+
+        let mut series = Series::new();
+        series.add(serie);
+        series.storage().update(SeriesStorage::Workspace(DataWorkspace::new()));
+
+        let command = series.as_commands().unwrap().pop_front().unwrap();
+        let path = command.to_string().split('"').nth(1).unwrap().to_string();
+
+        assert!(path.starts_with(&*DataWorkspace::new().root().to_string_lossy()));
+        assert_eq!(fs::read_to_string(&path).unwrap(), "0\t1\n");
+        assert_ne!(series.as_commands().unwrap().pop_front().unwrap(), command);
+
+        drop(command);
+        assert!(!std::path::Path::new(&path).exists());
+    }
+
+    fn ragged_series() -> Series<f64> {
+        let mut serie_1 = Serie::with_title("A");
+        serie_1.add(1.0);
+
+        let mut serie_2 = Serie::with_title("B");
+        serie_2.add(2.0);
+        serie_2.add(4.0);
+        serie_2.add(6.0);
+
+        let mut series = Series::new();
+        series.add(serie_1);
+        series.add(Serie::with_title("C"));
+        series.add(serie_2);
+        series
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_iterating_series_of_different_lengths() {
+        let mut it = ragged_series().iter();
+
+        assert_eq!(it.next(), Some((0, vec![Some(1.0), None, Some(2.0)])));
+        assert_eq!(it.next(), Some((1, vec![None, None, Some(4.0)])));
+        assert_eq!(it.next(), Some((2, vec![None, None, Some(6.0)])));
+        assert_eq!(it.next(), None);
+    }
+
+    #[test]
+    fn test_series_of_different_lengths_end_early() {
+        let mut commands = ragged_series().as_commands().unwrap();
+
+        assert_eq!(commands.pop_front().unwrap().to_string(), "$DATA << EOD\n0\t1\tNaN\t2\n1\tNaN\tNaN\t4\n2\tNaN\tNaN\t6\nEOD");
+        assert_eq!(commands.pop_front().unwrap(), GnuCommand::new("plot $DATA using 1:2 title 'A' with linespoint, $DATA using 1:3 title 'C' with linespoint, $DATA using 1:4 title 'B' with linespoint"));
+    }
+
+    #[test]
+    fn test_writing_series_of_different_lengths() {
+        let file = std::env::temp_dir().join("gnuplotter_ragged_series.txt");
+        let filename = file.to_string_lossy();
+
+        ragged_series().write_to_file(&filename).ok().unwrap();
+
+        assert_eq!(fs::read_to_string(&file).unwrap(), "0\t1\tNaN\t2\n1\tNaN\tNaN\t4\n2\tNaN\tNaN\t6\n");
+        fs::remove_file(file).unwrap();
+    }
+
+    #[test]
+    fn test_series_data_is_written_as_float64_records() {
+        let mut series = ragged_series();
+        series.format().update(SeriesFormat::float64());
+
+        let command = series.as_commands().unwrap().pop_front().unwrap();
+        let text = command.to_string();
+        let path = text.split('"').nth(1).unwrap().to_string();
+        let source = format!("\"{}\" binary format=\"%float64%float64%float64%float64\" endian=little", path);
+
+        assert_eq!(text, format!("plot {0} using 1:2 title 'A' with linespoint, {0} using 1:3 title 'C' with linespoint, {0} using 1:4 title 'B' with linespoint", source));
+        assert!(command.data().iter().all(|data| data.binary));
+
+        let values: Vec<Option<f64>> = fs::read(&path)
+            .unwrap()
+            .chunks(8)
+            .map(|bytes| f64::from_le_bytes(bytes.try_into().unwrap()))
+            .map(|value| (!value.is_nan()).then_some(value))
+            .collect();
+        assert_eq!(values, vec![
+            Some(0.0), Some(1.0), None, Some(2.0),
+            Some(1.0), None, None, Some(4.0),
+            Some(2.0), None, None, Some(6.0),
+        ]);
+
+        drop(command);
+        assert!(!std::path::Path::new(&path).exists());
+    }
+}
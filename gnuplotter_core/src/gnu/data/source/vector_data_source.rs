@@ -1,6 +1,7 @@
 use std::collections::VecDeque;
 use std::fs::File;
 use std::io::{Error, Write};
+use std::sync::Arc;
 use crate::gnu::data::source::DataSource;
 use crate::prelude::*;
 use crate::result::*;
@@ -51,29 +52,21 @@ impl Iterator for VectorDataSourceIterator {
 
 impl GnuCommandFactory for VectorDataSource {
     fn as_commands(&self) -> GnuCommandFactoryResult {
-        match File::create("data.txt") {
-            Ok(mut file) => {
+        let (data_file, mut file) = DataWorkspace::default().allocate("vector")?;
 
-                for i in 0..self.data.len() {
-                    if let Err(_) = write!(&mut file, "{}\t{}\n", i, self.data[i]) {
-                        return Err(
-                            GnuCommandFactoryError::io_error("Unable to write data to file.")
-                        );
-                    }
-                }
-
-                Ok(
-                    vec![
-                        GnuCommand::new("some command")
-                    ].into()
-                )
-            },
-            Err(_) => {
-                Err(
+        for i in 0..self.data.len() {
+            if writeln!(&mut file, "{}\t{}", i, self.data[i]).is_err() {
+                return Err(
                     GnuCommandFactoryError::io_error("Unable to write data to file.")
-                )
+                );
             }
         }
+
+        Ok(
+            vec![
+                GnuCommand::new("some command").reading(Arc::new(data_file))
+            ].into()
+        )
     }
 }
 
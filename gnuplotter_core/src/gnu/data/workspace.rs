@@ -0,0 +1,131 @@
+use std::env;
+use std::fs;
+use std::fs::{File, OpenOptions};
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use std::process;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use rand::random;
+use crate::prelude::*;
+
+/// Counts the files allocated by this process, so names stay unique across workspaces
+static ALLOCATED: AtomicUsize = AtomicUsize::new(0);
+
+/// The directory plots write their data files into. Every file gets a unique name, so plots
+/// rendered in parallel never overwrite each other's data. By default, files are written to a
+/// `gnuplotter` directory in the system's temporary directory.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DataWorkspace {
+    root: PathBuf,
+    keep_files: bool
+}
+
+impl Default for DataWorkspace {
+    fn default() -> Self {
+        DataWorkspace {
+            root: env::temp_dir().join("gnuplotter"),
+            keep_files: false
+        }
+    }
+}
+
+impl DataWorkspace {
+    pub fn new() -> Self {
+        DataWorkspace::default()
+    }
+
+    pub fn with_root(mut self, root: &str) -> Self {
+        self.root = root.into();
+        self
+    }
+
+    /// Keeps the data files once gnuplot is done with them, e.g. to inspect them while debugging
+    pub fn with_files_kept(mut self, keep_files: bool) -> Self {
+        self.keep_files = keep_files;
+        self
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Creates an empty file with a unique name starting with `name`. The file is removed when
+    /// it is dropped, unless the workspace keeps its files.
+    pub fn allocate(&self, name: &str) -> std::result::Result<(DataFile, File), GnuCommandFactoryError> {
+        fs::create_dir_all(&self.root)
+            .or(Err(GnuCommandFactoryError::io_error("Unable to create the data workspace")))?;
+
+        loop {
+            let allocated = ALLOCATED.fetch_add(1, Ordering::SeqCst);
+            let path = self.root.join(format!("{}_{}_{}_{:08x}.dat", name, process::id(), allocated, random::<u32>()));
+
+            match OpenOptions::new().write(true).create_new(true).open(&path) {
+                Ok(file) => return Ok((
+                    DataFile { path, keep: self.keep_files },
+                    file
+                )),
+                Err(error) if error.kind() == ErrorKind::AlreadyExists => continue,
+                Err(_) => return Err(GnuCommandFactoryError::io_error("Unable to create a data file"))
+            }
+        }
+    }
+}
+
+/// A data file in a `DataWorkspace`. Commands reading it hold on to it, which keeps it around
+/// until gnuplot has finished rendering them.
+#[derive(Debug, PartialEq)]
+pub struct DataFile {
+    path: PathBuf,
+    keep: bool
+}
+
+impl DataFile {
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl Drop for DataFile {
+    fn drop(&mut self) {
+        if !self.keep {
+            let _ = fs::remove_file(&self.path);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_allocated_files_are_unique_and_removed() {
+        let workspace = DataWorkspace::new();
+
+        let (first, _) = workspace.allocate("test").unwrap();
+        let (second, _) = workspace.allocate("test").unwrap();
+        let path = first.path().to_path_buf();
+
+        assert_ne!(first.path(), second.path());
+        assert!(path.starts_with(workspace.root()));
+        assert!(path.is_file());
+
+        drop(first);
+
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn test_files_can_be_kept() {
+        let root = env::temp_dir().join("gnuplotter_kept");
+        let workspace = DataWorkspace::new()
+            .with_root(&root.to_string_lossy())
+            .with_files_kept(true);
+
+        let (file, _) = workspace.allocate("kept").unwrap();
+        let path = file.path().to_path_buf();
+        drop(file);
+
+        assert!(path.is_file());
+        fs::remove_file(path).unwrap();
+    }
+}
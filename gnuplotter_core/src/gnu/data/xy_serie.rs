@@ -0,0 +1,96 @@
+/// A serie of points at explicit x values, e.g. measurements at irregular timestamps
+#[derive(Default, PartialEq, Debug, Clone)]
+pub struct XYSerie<X, Y>
+where
+    X: Default,
+    Y: Default
+{
+    title: Option<String>,
+    data: Vec<(X, Y)>
+}
+
+impl<X, Y> XYSerie<X, Y>
+where
+    X: Default + Clone,
+    Y: Default + Clone
+{
+    pub fn new() -> Self {
+        XYSerie::default()
+    }
+
+    pub fn with_title(title: &str) -> Self {
+        XYSerie {
+            title: Some(title.to_string()),
+            data: vec![]
+        }
+    }
+
+    /// Pairs up the x values with the y values. Values without a counterpart are dropped.
+    pub fn from_vectors(x: Vec<X>, y: Vec<Y>) -> Self {
+        XYSerie {
+            title: None,
+            data: x.into_iter().zip(y).collect()
+        }
+    }
+
+    pub fn add(&mut self, x: X, y: Y) {
+        self.data.push((x, y));
+    }
+
+    pub fn get(&self, index: usize) -> Option<(X, Y)> {
+        self.data.get(index).cloned()
+    }
+
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn iter(&self) -> std::slice::Iter<'_, (X, Y)> {
+        self.data.iter()
+    }
+
+    pub fn title(&self) -> &Option<String> {
+        &self.title
+    }
+}
+
+impl<X, Y> FromIterator<(X, Y)> for XYSerie<X, Y>
+where
+    X: Default,
+    Y: Default
+{
+    fn from_iter<I: IntoIterator<Item = (X, Y)>>(iter: I) -> Self {
+        XYSerie {
+            title: None,
+            data: iter.into_iter().collect()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_xy_serie_add() {
+        let mut serie = XYSerie::<f64, f64>::with_title("Load");
+        serie.add(0.5, 1.0);
+        serie.add(2.0, 4.0);
+
+        assert_eq!(serie.len(), 2);
+        assert_eq!(serie.get(1), Some((2.0, 4.0)));
+        assert_eq!(serie.get(2), None);
+        assert_eq!(serie.title(), &Some("Load".into()));
+    }
+
+    #[test]
+    fn test_xy_serie_from_vectors() {
+        let serie = XYSerie::from_vectors(vec![1, 10, 100], vec![0.1, 0.2]);
+
+        assert_eq!(serie.iter().cloned().collect::<Vec<_>>(), vec![(1, 0.1), (10, 0.2)]);
+    }
+}
@@ -0,0 +1,125 @@
+use crate::prelude::*;
+
+/// A collection of `XYSerie`s plotted together. Every serie has its own x values, so the series
+/// don't need to share the same x grid. Each serie is stored as a separate data set, which the plot
+/// selects using `index`.
+#[derive(Default, PartialEq, Debug, Clone)]
+pub struct XYSeries<X, Y>
+where
+    X: Default + Clone + ToString,
+    Y: Default + Clone + ToString
+{
+    data: Vec<XYSerie<X, Y>>,
+    storage: SeriesStorage
+}
+
+impl<X, Y> XYSeries<X, Y>
+where
+    X: Default + Clone + ToString,
+    Y: Default + Clone + ToString
+{
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn add(&mut self, serie: XYSerie<X, Y>) {
+        self.data.push(serie);
+    }
+
+    /// The number of points of the longest serie
+    pub fn len(&self) -> usize {
+        self.data.iter().map(XYSerie::len).max().unwrap_or(0)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn storage(&mut self) -> &mut SeriesStorage {
+        &mut self.storage
+    }
+
+    /// The series that have points, as they can't be plotted otherwise
+    fn plotted(&self) -> impl Iterator<Item = &XYSerie<X, Y>> {
+        self.data.iter().filter(|serie| !serie.is_empty())
+    }
+
+    /// The points of every serie as tab separated rows. Data sets are separated by two blank rows.
+    fn rows(&self) -> Vec<String> {
+        let mut rows = vec![];
+        for (index, serie) in self.plotted().enumerate() {
+            if index > 0 {
+                rows.push("".into());
+                rows.push("".into());
+            }
+            for (x, y) in serie.iter() {
+                rows.push(format!("{}\t{}", x.to_string(), y.to_string()));
+            }
+        }
+
+        rows
+    }
+}
+
+impl<X, Y> GnuCommandFactory for XYSeries<X, Y>
+where
+    X: Default + Clone + ToString,
+    Y: Default + Clone + ToString
+{
+    fn as_commands(&self) -> GnuCommandFactoryResult {
+        self.storage.commands(&self.rows(), |source| {
+            let mut command = "plot ".to_string();
+            for (index, serie) in self.plotted().enumerate() {
+                let title = serie.title().clone().unwrap_or_default();
+                command += &format!("{} index {} using 1:2 title '{}' with linespoint, ", source, index, title);
+            }
+
+            command
+                .strip_suffix(", ")
+                .map(String::from)
+                .ok_or(GnuCommandFactoryError::message("Unable to strip suffix"))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn series() -> XYSeries<f64, f64> {
+        let mut load = XYSerie::with_title("Load");
+        load.add(0.5, 1.0);
+        load.add(2.0, 4.0);
+
+        let mut idle = XYSerie::new();
+        idle.add(1.0, 0.1);
+        idle.add(1.5, 0.2);
+        idle.add(8.0, 0.3);
+
+        let mut series = XYSeries::new();
+        series.add(load);
+        series.add(XYSerie::new());
+        series.add(idle);
+        series
+    }
+
+    #[test]
+    fn test_xy_series_data_sets() {
+        let mut commands = series().as_commands().unwrap();
+
+        assert_eq!(commands.pop_front().unwrap().to_string(), "$DATA << EOD\n0.5\t1\n2\t4\n\n\n1\t0.1\n1.5\t0.2\n8\t0.3\nEOD");
+        assert_eq!(commands.pop_front().unwrap(), GnuCommand::new("plot $DATA index 0 using 1:2 title 'Load' with linespoint, $DATA index 1 using 1:2 title '' with linespoint"));
+        assert!(commands.is_empty());
+    }
+
+    #[test]
+    fn test_xy_series_len() {
+        assert_eq!(series().len(), 3);
+        assert!(XYSeries::<f64, f64>::new().is_empty());
+    }
+
+    #[test]
+    fn test_xy_series_without_data_fail() {
+        assert!(XYSeries::<f64, f64>::new().as_commands().is_err());
+    }
+}
@@ -1,15 +1,34 @@
 use std::collections::VecDeque;
 use std::fmt::{Debug, Display, Formatter};
-use std::io::Write;
-use std::process::{Command, Stdio, Child, ChildStdin};
+use std::io::{Read, Write};
+use std::process::{Stdio, Child, ChildStdin, ExitStatus};
 use crate::prelude::*;
+use crate::gnu::render::diagnostic::Transcript;
+use crate::gnu::render::limits::{kill, Limits, StderrLines};
+
+#[cfg(feature = "tokio")]
+pub mod asynchronous;
+pub mod backend;
+pub mod batch;
+pub mod diagnostic;
+pub mod executable;
+pub mod limits;
+pub mod session;
 
 pub struct Render;
 
+/// A plot rendered into memory, along with the MIME type of the terminal that produced it and the
+/// warnings gnuplot reported
+#[derive(Debug, Clone, PartialEq)]
+pub struct RenderedPlot {
+    pub bytes: Vec<u8>,
+    pub mime_type: &'static str,
+    pub warnings: Vec<GnuplotDiagnostic>
+}
+
 /// Documents in which ways the rendering of a plot can fail. Rendering would most likely fail if
-/// gnuplot is not installed or reachable for some reason. There is no error associated with errors
-/// in gnuplot syntax. The only thing these errors indicate is some kind of success or error in the
-/// process of communicating with gnuplot.
+/// gnuplot is not installed or reachable for some reason, or when gnuplot reports an error in the
+/// commands it was sent.
 pub enum RenderError {
     /// When calling `.as_commands()` fails with an error message
     CommandGenerationFailed(String),
@@ -22,7 +41,31 @@ pub enum RenderError {
     /// We need to wait for gnuplot to finish rendering before killing it. This indicates that this
     /// waiting failed. This might indicate an error in gnuplot command syntax that gnuplot is
     /// escalating.
-    WaitingForGnuPlotFailed
+    WaitingForGnuPlotFailed,
+    /// The output gnuplot wrote to its STDOUT could not be read as text. Verify the terminal
+    /// produces a text plot, such as the `Dumb` terminal does.
+    OutputNotText,
+    /// Gnuplot stopped before confirming it finished rendering a frame. This most likely indicates
+    /// an error in gnuplot command syntax.
+    SynchronizationFailed,
+    /// The terminal is unable to write its plot to STDOUT, so it can't be rendered into memory.
+    /// Contains the name of the terminal.
+    OutputNotCapturable(&'static str),
+    /// The version of gnuplot could not be read from the output of `gnuplot --version`, which is
+    /// contained.
+    VersionUnknown(String),
+    /// Rendering took longer than the timeout allowed, so gnuplot was killed
+    TimedOut,
+    /// Rendering was stopped using a `Cancellation`, so gnuplot was killed
+    Cancelled,
+    /// Gnuplot reported an error, such as a syntax error. It points back to the command gnuplot
+    /// was reading when it failed.
+    GnuplotError(GnuplotDiagnostic),
+    /// Gnuplot exited unsuccessfully without reporting an error we recognize
+    GnuplotExited {
+        code: Option<i32>,
+        stderr: String
+    }
 }
 
 impl Display for RenderError {
@@ -32,7 +75,16 @@ impl Display for RenderError {
             RenderError::WritingCommandFailed => "Failed to `write` command to GnuPlot".into(),
             RenderError::GnuSpawnFailed => "Failed to spawn GnuPlot. Verify it is correctly installed and available".into(),
             RenderError::GnuSTDINNotAccessible => "GnuPlot STDIN cannot be accessed".into(),
-            RenderError::WaitingForGnuPlotFailed => "Waiting for Gnu Plot failed. Check your command syntax for errors.".into()
+            RenderError::WaitingForGnuPlotFailed => "Waiting for Gnu Plot failed. Check your command syntax for errors.".into(),
+            RenderError::OutputNotText => "GnuPlot output is not valid UTF-8 text".into(),
+            RenderError::SynchronizationFailed => "GnuPlot stopped before it finished rendering. Check your command syntax for errors.".into(),
+            RenderError::OutputNotCapturable(name) => format!("The {} terminal can't write its output to STDOUT", name),
+            RenderError::VersionUnknown(output) => format!("Unable to detect the GnuPlot version from `{}`", output),
+            RenderError::TimedOut => "GnuPlot did not finish rendering before the timeout".into(),
+            RenderError::Cancelled => "Rendering was cancelled".into(),
+            RenderError::GnuplotError(diagnostic) => format!("GnuPlot reported an error: {}", diagnostic),
+            RenderError::GnuplotExited { code: Some(code), stderr } => format!("GnuPlot exited with status {}: {}", code, stderr.trim()),
+            RenderError::GnuplotExited { code: None, stderr } => format!("GnuPlot was terminated: {}", stderr.trim())
         };
         f.write_fmt(format_args!("{}", msg))
     }
@@ -59,33 +111,780 @@ impl From<GnuCommandFactoryError> for RenderError {
 
 pub type RenderResult<T> = std::result::Result<T, RenderError>;
 
-impl Render {
-    pub fn render(commands: VecDeque<GnuCommand>) -> RenderResult<()> {
+/// What happened while gnuplot ran the commands written to it
+struct Execution {
+    output: Vec<u8>,
+    transcript: Transcript,
+    stderr: String,
+    status: ExitStatus,
+    written: RenderResult<()>
+}
 
-        let commands: Vec<GnuCommand> = commands.into();
+/// Line gnuplot is asked to print once it has processed all commands sent before it
+const SYNCHRONIZATION_MARKER: &str = "gnuplotter:synchronized";
 
-        let mut gnu = Command::new("gnuplot")
+impl Render {
+    fn spawn(executable: &Executable, stdout: Stdio, stderr: Stdio) -> RenderResult<Child> {
+        executable
+            .command()
             .arg("-p")
             .stdin(Stdio::piped())
+            .stdout(stdout)
+            .stderr(stderr)
             .spawn()
-            .map_err(|_| RenderError::GnuSpawnFailed)?;
-        let stdin = gnu.stdin.as_mut().ok_or(RenderError::GnuSTDINNotAccessible)?;
+            .map_err(|_| RenderError::GnuSpawnFailed)
+    }
 
-        writeln!(stdin, "clear").or(Err(RenderError::WritingCommandFailed))?;
+    fn write_commands<W: Write>(stdin: &mut W, transcript: &mut Transcript, commands: Vec<GnuCommand>) -> RenderResult<()> {
+        transcript.write_line(stdin, "clear")?;
         for command in commands {
-            writeln!(stdin, "{}", command).or(Err(RenderError::WritingCommandFailed))?;
+            transcript.write_command(stdin, command)?;
+        }
+        transcript.write_line(stdin, "exit")
+    }
+
+    /// Fails on the first error gnuplot reported and returns the warnings otherwise
+    fn diagnose(transcript: &Transcript, stderr: &str) -> RenderResult<Vec<GnuplotDiagnostic>> {
+        let (warnings, errors): (Vec<GnuplotDiagnostic>, Vec<GnuplotDiagnostic>) = transcript
+            .diagnose(stderr)
+            .into_iter()
+            .partition(GnuplotDiagnostic::is_warning);
+
+        match errors.into_iter().next() {
+            Some(error) => Err(RenderError::GnuplotError(error)),
+            None => Ok(warnings)
+        }
+    }
+
+    /// Decides on the outcome of a render once gnuplot has exited. The first error gnuplot
+    /// reported fails the render, as does an unsuccessful exit. Otherwise, the warnings are
+    /// returned.
+    fn conclude(transcript: &Transcript, stderr: &str, status: ExitStatus) -> RenderResult<Vec<GnuplotDiagnostic>> {
+        let warnings = Render::diagnose(transcript, stderr)?;
+        if !status.success() {
+            return Err(RenderError::GnuplotExited { code: status.code(), stderr: stderr.into() });
+        }
+
+        Ok(warnings)
+    }
+
+    /// Writes the commands to gnuplot, capturing its STDERR to report errors and warnings. Gnuplot
+    /// is killed when the limits are exceeded.
+    fn execute(
+        executable: &Executable,
+        limits: &Limits,
+        commands: VecDeque<GnuCommand>,
+        stdout: Stdio
+    ) -> RenderResult<Execution> {
+
+        limits.check()?;
+        executable.check(&commands)?;
+        let commands: Vec<GnuCommand> = commands.into();
+
+        let mut gnu = Render::spawn(executable, stdout, Stdio::piped())?;
+        let mut stdin = gnu.stdin.take().ok_or(RenderError::GnuSTDINNotAccessible)?;
+        let stderr = StderrLines::new(gnu.stderr.take().ok_or(RenderError::WaitingForGnuPlotFailed)?);
+        let stdout = gnu.stdout.take().map(|mut stdout| std::thread::spawn(move || {
+            let mut output = vec![];
+            let _ = stdout.read_to_end(&mut output);
+            output
+        }));
+
+        // commands are written from another thread, so gnuplot can't block on a full STDOUT or
+        // STDERR pipe while we are still writing to its STDIN
+        let writer = std::thread::spawn(move || {
+            let mut transcript = Transcript::new();
+            let written = Render::write_commands(&mut stdin, &mut transcript, commands);
+            (transcript, written)
+        });
+        let status = limits.wait(&mut gnu)?;
+        let output = match stdout {
+            Some(reader) => reader.join().or(Err(RenderError::WaitingForGnuPlotFailed))?,
+            None => vec![]
+        };
+        let (transcript, written) = writer.join().or(Err(RenderError::WritingCommandFailed))?;
+
+        Ok(Execution {
+            output,
+            transcript,
+            stderr: stderr.rest(),
+            status,
+            written
+        })
+    }
+
+    /// Renders the commands, returning what gnuplot wrote to STDOUT if it was captured, together
+    /// with the warnings
+    fn run(
+        executable: &Executable,
+        limits: &Limits,
+        commands: VecDeque<GnuCommand>,
+        stdout: Stdio
+    ) -> RenderResult<(Vec<u8>, Vec<GnuplotDiagnostic>)> {
+        let execution = Render::execute(executable, limits, commands, stdout)?;
+
+        // gnuplot exits on the first error, which makes writing the remaining commands fail, so
+        // the error gnuplot reported takes precedence
+        let warnings = Render::conclude(&execution.transcript, &execution.stderr, execution.status)?;
+        execution.written?;
+
+        Ok((execution.output, warnings))
+    }
+
+    /// Runs the commands and returns every error and warning gnuplot reported, in the order it
+    /// reported them. Only failing to run gnuplot, or gnuplot exiting unsuccessfully without
+    /// reporting an error, fails.
+    fn run_check(
+        executable: &Executable,
+        limits: &Limits,
+        commands: VecDeque<GnuCommand>
+    ) -> RenderResult<Vec<GnuplotDiagnostic>> {
+        let execution = Render::execute(executable, limits, commands, Stdio::null())?;
+        let diagnostics = execution.transcript.diagnose(&execution.stderr);
+
+        if diagnostics.iter().all(GnuplotDiagnostic::is_warning) {
+            if !execution.status.success() {
+                return Err(RenderError::GnuplotExited { code: execution.status.code(), stderr: execution.stderr });
+            }
+            execution.written?;
+        }
+
+        Ok(diagnostics)
+    }
+
+    /// Renders the commands and returns the warnings gnuplot reported. Use a `GnuplotBackend` to
+    /// render with another executable than the default one.
+    pub fn render(commands: VecDeque<GnuCommand>) -> RenderResult<Vec<GnuplotDiagnostic>> {
+        let (_, warnings) = Render::run(&Executable::default(), &Limits::default(), commands, Stdio::inherit())?;
+        Ok(warnings)
+    }
+
+    /// Renders the commands while capturing everything gnuplot writes to its STDOUT. Terminals
+    /// without an output file, such as `Dumb` or `Sixel`, write their plot there. Returns the
+    /// output along with the warnings gnuplot reported.
+    pub fn render_to_bytes(commands: VecDeque<GnuCommand>) -> RenderResult<(Vec<u8>, Vec<GnuplotDiagnostic>)> {
+        Render::run(&Executable::default(), &Limits::default(), commands, Stdio::piped())
+    }
+
+    /// Renders the commands and returns the text gnuplot wrote to its STDOUT, such as a plot
+    /// produced by the `Dumb` terminal, along with the warnings gnuplot reported.
+    pub fn render_to_string(commands: VecDeque<GnuCommand>) -> RenderResult<(String, Vec<GnuplotDiagnostic>)> {
+        let (output, warnings) = Render::render_to_bytes(commands)?;
+        let text = String::from_utf8(output).or(Err(RenderError::OutputNotText))?;
+
+        Ok((text, warnings))
+    }
+
+    /// Builds the commands running a plot against gnuplot's `unknown` terminal, which produces no
+    /// output. The terminal and output selected by the plot itself are ignored. Returns the index
+    /// each command had among the plot's commands, if it was one of them.
+    fn check_commands(commands: VecDeque<GnuCommand>) -> (VecDeque<GnuCommand>, Vec<Option<usize>>) {
+        let mut check = VecDeque::from([GnuCommand::new("set terminal unknown")]);
+        let mut origins = vec![None];
+        for (index, command) in commands.into_iter().enumerate() {
+            if !command.selects_terminal() {
+                check.push_back(command);
+                origins.push(Some(index));
+            }
+        }
+
+        (check, origins)
+    }
+
+    /// Runs the commands without producing output and returns every error and warning gnuplot
+    /// reported about them. Gnuplot stops at the first error, which is returned as the last
+    /// diagnostic. Diagnostics point to the index of their command among the given commands.
+    pub fn check(commands: VecDeque<GnuCommand>, backend: &dyn Backend) -> RenderResult<Vec<GnuplotDiagnostic>> {
+        let (check, origins) = Render::check_commands(commands);
+        let mut diagnostics = backend.check(check)?;
+        for diagnostic in &mut diagnostics {
+            diagnostic.index = diagnostic.index.and_then(|index| origins.get(index).copied().flatten());
+        }
+
+        Ok(diagnostics)
+    }
+
+    /// Builds the commands rendering a plot to STDOUT using the terminal. The terminal and output
+    /// selected by the plot itself are ignored.
+    fn memory_commands(commands: VecDeque<GnuCommand>, terminal: &dyn Terminal) -> RenderResult<VecDeque<GnuCommand>> {
+        if !terminal.capabilities().stdout {
+            return Err(RenderError::OutputNotCapturable(terminal.name()));
+        }
+
+        let mut memory = mark_typesetting(terminal, terminal.term_commands()?);
+        memory.push_back(GnuCommand::new("set output"));
+        memory.extend(commands.into_iter().filter(|command| !command.selects_terminal()));
+
+        Ok(GnuCommand::typeset(memory))
+    }
+
+    /// Renders the commands using the terminal and returns the plot without writing it to a file
+    pub fn render_to_memory(commands: VecDeque<GnuCommand>, terminal: &dyn Terminal) -> RenderResult<RenderedPlot> {
+        let mime_type = terminal.mime_type();
+        let commands = Render::memory_commands(commands, terminal)?;
+        let (bytes, warnings) = Render::render_to_bytes(commands)?;
+
+        Ok(RenderedPlot {
+            bytes,
+            mime_type,
+            warnings
+        })
+    }
+
+    /// Builds the commands rendering a plot to each of the terminals in turn. The terminal and
+    /// output selected by the plot itself are replaced by those of each terminal.
+    fn export_commands(commands: VecDeque<GnuCommand>, terminals: &[&dyn Terminal]) -> GnuCommandFactoryResult {
+        let plot: Vec<GnuCommand> = commands
+            .into_iter()
+            .filter(|command| !command.selects_terminal())
+            .collect();
+
+        let mut export = VecDeque::new();
+        for terminal in terminals {
+            export.append(&mut mark_typesetting(*terminal, terminal.as_commands()?));
+            export.extend(plot.iter().cloned());
+            export.push_back(GnuCommand::new("unset output"));
+        }
+
+        Ok(GnuCommand::typeset(export))
+    }
+
+    /// Renders the same commands to several terminals using a single gnuplot process
+    pub fn export(commands: VecDeque<GnuCommand>, terminals: &[&dyn Terminal]) -> RenderResult<Vec<GnuplotDiagnostic>> {
+        let commands = Render::export_commands(commands, terminals)?;
+        Render::render(commands)
+    }
+
+    /// Asks gnuplot to print a marker on its STDERR, which is unbuffered, and waits until it
+    /// appears. Gnuplot has then finished all commands sent before, including reading their data.
+    /// Anything else gnuplot prints in the meantime is collected in `output`.
+    fn synchronize(
+        stdin: &mut ChildStdin,
+        stderr: &StderrLines,
+        limits: &Limits,
+        transcript: &mut Transcript,
+        output: &mut String
+    ) -> RenderResult<()> {
+        // a plot may have redirected `print` to a file, where the marker would never be seen
+        transcript.write_line(stdin, "set print")?;
+        transcript.write_line(stdin, &format!("print \"{}\"", SYNCHRONIZATION_MARKER))?;
+        stdin.flush().or(Err(RenderError::WritingCommandFailed))?;
+
+        loop {
+            match stderr.next(limits)? {
+                None => return Err(RenderError::SynchronizationFailed),
+                Some(line) if line.trim_end() == SYNCHRONIZATION_MARKER => return Ok(()),
+                Some(line) => output.push_str(&line)
+            }
+        }
+    }
+
+    /// Drops the commands selecting a terminal or output once an animation has started, because
+    /// they would restart it. Returns the remaining commands of the frame.
+    fn frame_commands(commands: VecDeque<GnuCommand>, animating: &mut bool) -> Vec<GnuCommand> {
+        let commands: Vec<GnuCommand> = commands
+            .into_iter()
+            .filter(|command| !(*animating && command.selects_terminal()))
+            .collect();
+        *animating = *animating || commands.iter().any(GnuCommand::selects_terminal);
+
+        commands
+    }
+
+    fn write_frames(
+        stdin: &mut ChildStdin,
+        stderr: &StderrLines,
+        limits: &Limits,
+        transcript: &mut Transcript,
+        output: &mut String,
+        setup: VecDeque<GnuCommand>,
+        frames: &mut dyn Iterator<Item = RenderResult<VecDeque<GnuCommand>>>
+    ) -> RenderResult<()> {
+        let mut animating = false;
+        transcript.write_line(stdin, "clear")?;
+        for command in Render::frame_commands(setup, &mut animating) {
+            transcript.write_command(stdin, command)?;
         }
-        writeln!(stdin, "exit").or(Err(RenderError::WritingCommandFailed))?;
 
-        gnu.wait().or(Err(RenderError::WaitingForGnuPlotFailed))?;
+        // frames are generated one at a time, because each of them may overwrite the data file
+        // the previous frame is still reading from
+        for frame in frames {
+            for command in Render::frame_commands(frame?, &mut animating) {
+                transcript.write_command(stdin, command)?;
+            }
+            Render::synchronize(stdin, stderr, limits, transcript, output)?;
+        }
+        transcript.write_line(stdin, "exit")?;
 
         Ok(())
     }
+
+    /// Renders the setup followed by each frame, generating the commands of a frame only once
+    /// gnuplot finished the previous one.
+    fn render_frames(
+        executable: &Executable,
+        limits: &Limits,
+        setup: VecDeque<GnuCommand>,
+        frames: &mut dyn Iterator<Item = GnuCommandFactoryResult>
+    ) -> RenderResult<Vec<GnuplotDiagnostic>> {
+        limits.check()?;
+        executable.check(&setup)?;
+        let mut frames = frames.map(|frame| {
+            let frame = frame?;
+            executable.check(&frame)?;
+            Ok(frame)
+        });
+
+        let mut gnu = Render::spawn(executable, Stdio::inherit(), Stdio::piped())?;
+        let mut stdin = gnu.stdin.take().ok_or(RenderError::GnuSTDINNotAccessible)?;
+        let stderr = StderrLines::new(gnu.stderr.take().ok_or(RenderError::SynchronizationFailed)?);
+
+        let mut transcript = Transcript::new();
+        let mut output = String::new();
+        let written = Render::write_frames(&mut stdin, &stderr, limits, &mut transcript, &mut output, setup, &mut frames);
+        drop(stdin);
+        if let Err(error @ (RenderError::TimedOut | RenderError::Cancelled)) = written {
+            kill(&mut gnu);
+            return Err(error);
+        }
+
+        // once gnuplot exits, whatever it reported after the last frame is collected as well
+        let status = limits.wait(&mut gnu)?;
+        output.push_str(&stderr.rest());
+        let warnings = Render::conclude(&transcript, &output, status)?;
+        written?;
+
+        Ok(warnings)
+    }
+
+    /// Renders each frame as a separate plot into a single output, such as an animated gif
+    /// produced by the `GifAnimate` terminal. Frames may be complete plots, in which case only the
+    /// terminal and output of the first frame are used.
+    pub fn render_animation<F, I>(frames: I) -> RenderResult<Vec<GnuplotDiagnostic>>
+    where
+        F: GnuCommandFactory,
+        I: IntoIterator<Item = F>
+    {
+        let mut frames = frames.into_iter().map(|frame| frame.as_commands());
+        Render::render_frames(&Executable::default(), &Limits::default(), VecDeque::new(), &mut frames)
+    }
 }
 
+/// Renders a plot. Rendering goes through the default backend, which runs gnuplot unless another
+/// backend is set using `set_default_backend()`. The `_with` variants render using the backend they
+/// are given instead.
+///
+/// The `_async` variants, available with the `tokio` feature, are the exception: they always run
+/// the default `Executable` using tokio's processes. They ignore the default backend, so they are
+/// neither recorded by a `RecordingBackend` nor limited by the timeout or cancellation of a
+/// `GnuplotBackend`. Use `tokio::time::timeout` to limit them instead, which kills gnuplot once the
+/// render is dropped.
 pub trait CanRender: GnuCommandFactory {
-    fn render(&self) -> RenderResult<()> {
+    /// Renders the plot and returns the warnings gnuplot reported
+    fn render(&self) -> RenderResult<Vec<GnuplotDiagnostic>> {
+        self.render_with(default_backend().as_ref())
+    }
+
+    /// Renders the plot using the backend instead of the default one, e.g. a `RecordingBackend`
+    /// in a test.
+    fn render_with(&self, backend: &dyn Backend) -> RenderResult<Vec<GnuplotDiagnostic>> {
         let commands = self.as_commands()?;
-        Render::render(commands)
+        backend.render(commands)
+    }
+
+    /// Validates the plot by running its commands against gnuplot without producing any output,
+    /// e.g. in CI. Every error and warning is returned as a diagnostic pointing to the index of the
+    /// command it originates from in `as_commands()`. The plot doesn't need an output file, in
+    /// which case the index is in `as_commands_without_output()` instead.
+    fn check(&self) -> RenderResult<Vec<GnuplotDiagnostic>> {
+        self.check_with(default_backend().as_ref())
+    }
+
+    fn check_with(&self, backend: &dyn Backend) -> RenderResult<Vec<GnuplotDiagnostic>> {
+        let commands = self.as_commands().or_else(|_| self.as_commands_without_output())?;
+        Render::check(commands, backend)
+    }
+
+    /// Renders the plot without blocking the thread while gnuplot runs
+    #[cfg(feature = "tokio")]
+    fn render_async(&self) -> impl std::future::Future<Output = RenderResult<Vec<GnuplotDiagnostic>>> + Send
+    where
+        Self: Sized
+    {
+        let commands = self.as_commands();
+        async move { Render::render_async(commands?).await }
+    }
+
+    /// Renders the plot without blocking the thread and returns what gnuplot wrote to STDOUT, along
+    /// with the warnings gnuplot reported
+    #[cfg(feature = "tokio")]
+    fn render_to_bytes_async(&self) -> impl std::future::Future<Output = RenderResult<(Vec<u8>, Vec<GnuplotDiagnostic>)>> + Send
+    where
+        Self: Sized
+    {
+        let commands = self.as_commands();
+        async move { Render::render_to_bytes_async(commands?).await }
+    }
+
+    /// Renders the plot using a gnuplot process that is kept alive between plots, which is much
+    /// faster when rendering many plots in a row.
+    fn render_in_session(&self, session: &mut GnuplotSession) -> RenderResult<Vec<GnuplotDiagnostic>> {
+        let commands = self.as_commands()?;
+        session.render(commands)
+    }
+
+    /// Renders the plot and returns what gnuplot wrote to STDOUT, e.g. a text plot produced by
+    /// the `Dumb` terminal, along with the warnings gnuplot reported.
+    fn render_to_string(&self) -> RenderResult<(String, Vec<GnuplotDiagnostic>)> {
+        let (output, warnings) = self.render_to_bytes()?;
+        let text = String::from_utf8(output).or(Err(RenderError::OutputNotText))?;
+
+        Ok((text, warnings))
+    }
+
+    /// Renders the plot and returns the raw bytes gnuplot wrote to STDOUT, e.g. the escape
+    /// sequences produced by the `Sixel` terminal, along with the warnings gnuplot reported.
+    fn render_to_bytes(&self) -> RenderResult<(Vec<u8>, Vec<GnuplotDiagnostic>)> {
+        self.render_to_bytes_with(default_backend().as_ref())
+    }
+
+    fn render_to_bytes_with(&self, backend: &dyn Backend) -> RenderResult<(Vec<u8>, Vec<GnuplotDiagnostic>)> {
+        let commands = self.as_commands()?;
+        backend.render_to_bytes(commands)
+    }
+
+    /// Renders the plot using the terminal and returns the image in memory, e.g. a png to serve
+    /// over HTTP. The terminal's output file is ignored and no file is written for the image.
+    fn render_to_memory(&self, terminal: &dyn Terminal) -> RenderResult<RenderedPlot> {
+        self.render_to_memory_with(terminal, default_backend().as_ref())
+    }
+
+    fn render_to_memory_with(&self, terminal: &dyn Terminal, backend: &dyn Backend) -> RenderResult<RenderedPlot> {
+        let commands = Render::memory_commands(self.as_commands_without_output()?, terminal)?;
+        let (bytes, warnings) = backend.render_to_bytes(commands)?;
+
+        Ok(RenderedPlot {
+            bytes,
+            mime_type: terminal.mime_type(),
+            warnings
+        })
+    }
+
+    /// Renders the plot to each of the terminals, e.g. a png, an svg and a pdf of the same figure.
+    /// Each terminal needs its own output file, while the terminal and output configured by the
+    /// plot itself are ignored. Data is only written once.
+    fn export(&self, terminals: &[&dyn Terminal]) -> RenderResult<Vec<GnuplotDiagnostic>> {
+        self.export_with(terminals, default_backend().as_ref())
+    }
+
+    fn export_with(&self, terminals: &[&dyn Terminal], backend: &dyn Backend) -> RenderResult<Vec<GnuplotDiagnostic>> {
+        let commands = Render::export_commands(self.as_commands_without_output()?, terminals)?;
+        backend.render(commands)
+    }
+
+    /// Writes the plot as a gnuplot script named `NAME.gp` into the directory, along with its data,
+    /// so `gnuplot NAME.gp` run from within the directory reproduces the plot.
+    fn export_script(&self, directory: &str, name: &str, data: ScriptData) -> ScriptResult {
+        let commands = self.as_commands()?;
+        Script::export(commands, directory, name, data)
+    }
+
+    /// Renders the plot once, followed by every frame as an additional plot, e.g. snapshots of a
+    /// `Series<T>` of an evolving simulation. Using the `GifAnimate` terminal, this produces an
+    /// animated gif.
+    fn render_animation<F, I>(&self, frames: I) -> RenderResult<Vec<GnuplotDiagnostic>>
+    where
+        F: GnuCommandFactory,
+        I: IntoIterator<Item = F>,
+        Self: Sized
+    {
+        self.render_animation_with(frames, default_backend().as_ref())
+    }
+
+    fn render_animation_with<F, I>(&self, frames: I, backend: &dyn Backend) -> RenderResult<Vec<GnuplotDiagnostic>>
+    where
+        F: GnuCommandFactory,
+        I: IntoIterator<Item = F>,
+        Self: Sized
+    {
+        let commands = self.as_commands()?;
+        let mut frames = frames.into_iter().map(|frame| frame.as_commands());
+        backend.render_frames(commands, &mut frames)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(unix)]
+    fn exit_status(code: i32) -> ExitStatus {
+        use std::os::unix::process::ExitStatusExt;
+        ExitStatus::from_raw(code << 8)
+    }
+
+    fn transcript() -> Transcript {
+        let mut transcript = Transcript::new();
+        let mut sink = vec![];
+        transcript.write_line(&mut sink, "clear").unwrap();
+        transcript.write_command(&mut sink, GnuCommand::new("plot foo")).unwrap();
+        transcript.write_line(&mut sink, "exit").unwrap();
+
+        transcript
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_gnuplot_errors_fail_the_render() {
+        let result = Render::conclude(&transcript(), "         line 2: undefined variable: foo", exit_status(1));
+
+        match result {
+            Err(RenderError::GnuplotError(diagnostic)) => {
+                assert_eq!(diagnostic.index, Some(0));
+                assert_eq!(diagnostic.command, Some(GnuCommand::new("plot foo")));
+            },
+            _ => panic!("Expected a gnuplot error")
+        }
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_an_unsuccessful_exit_fails_the_render() {
+        let result = Render::conclude(&transcript(), "something went wrong", exit_status(1));
+
+        assert!(matches!(result, Err(RenderError::GnuplotExited { code: Some(1), .. })));
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_warnings_are_returned() {
+        let warnings = Render::conclude(&transcript(), "line 2: warning: empty x range", exit_status(0)).unwrap();
+
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].message, "warning: empty x range");
+    }
+
+    #[test]
+    fn test_frames_keep_the_terminal_of_the_first_frame() {
+        let frame = || -> VecDeque<GnuCommand> {
+            vec![
+                GnuCommand::new("set output './animation.gif'"),
+                GnuCommand::new("set term gif enhanced animate"),
+                GnuCommand::new("plot sin(x)"),
+            ].into()
+        };
+
+        let mut animating = false;
+        let first = Render::frame_commands(frame(), &mut animating);
+        let second = Render::frame_commands(frame(), &mut animating);
+
+        assert_eq!(first.len(), 3);
+        assert_eq!(second, vec![GnuCommand::new("plot sin(x)")]);
+    }
+
+    #[test]
+    fn test_frames_without_terminal_are_kept_whole() {
+        let mut animating = false;
+        let commands = Render::frame_commands(vec![GnuCommand::new("plot sin(x)")].into(), &mut animating);
+
+        assert_eq!(commands, vec![GnuCommand::new("plot sin(x)")]);
+        assert!(!animating);
+    }
+
+    struct Float64Plot(Series<f64>);
+
+    impl GnuCommandFactory for Float64Plot {
+        fn as_commands(&self) -> GnuCommandFactoryResult {
+            self.0.as_commands()
+        }
+    }
+
+    impl CanRender for Float64Plot {}
+
+    #[test]
+    fn test_exporting_a_script_copies_binary_data_even_when_inlining() {
+        let mut serie = Serie::with_title("A");
+        serie.add(1.0);
+        serie.add(2.0);
+        let mut series = Series::new();
+        series.add(serie);
+        series.format().update(SeriesFormat::float64());
+        let directory = std::env::temp_dir().join("gnuplotter_export_script_float64");
+        let _ = std::fs::remove_dir_all(&directory);
+
+        let bundle = Float64Plot(series)
+            .export_script(&directory.to_string_lossy(), "plot", ScriptData::Inline)
+            .ok()
+            .unwrap();
+
+        assert_eq!(bundle.data_files.len(), 1);
+        let name = bundle.data_files[0].file_name().unwrap().to_string_lossy().to_string();
+        let script = std::fs::read_to_string(&bundle.script).unwrap();
+        assert!(script.contains(&format!("plot '{}' binary format=\"%float64%float64\" endian=little", name)));
+        assert_eq!(std::fs::read(&bundle.data_files[0]).unwrap().len(), 2 * 2 * 8);
+        std::fs::remove_dir_all(directory).unwrap();
+    }
+
+    #[test]
+    fn test_exporting_renders_the_plot_to_every_terminal() {
+        let commands: VecDeque<GnuCommand> = vec![
+            GnuCommand::new("set output './plot.png'"),
+            GnuCommand::new("set term pngcairo enhanced"),
+            GnuCommand::new("set title 'export'"),
+            GnuCommand::new("plot sin(x)"),
+        ].into();
+
+        let mut svg = Svg::default();
+        svg.output().update("./plot.svg");
+        let mut pdf = PdfCairo::default();
+        pdf.output().update("./plot.pdf");
+
+        let export = Render::export_commands(commands, &[&svg, &pdf]).unwrap();
+        let export: Vec<String> = export.iter().map(GnuCommand::to_string).collect();
+
+        assert_eq!(export, vec![
+            "set output './plot.svg'",
+            "set term svg enhanced",
+            "set title 'export'",
+            "plot sin(x)",
+            "unset output",
+            "set output './plot.pdf'",
+            "set term pdfcairo enhanced",
+            "set title 'export'",
+            "plot sin(x)",
+            "unset output",
+        ]);
+    }
+
+    #[test]
+    fn test_exporting_writes_latex_math_verbatim_for_latex_terminals_only() {
+        let commands: VecDeque<GnuCommand> = Title::new("$\\alpha$").as_commands().unwrap();
+
+        let mut png = PngCairo::default();
+        png.output().update("./plot.png");
+        let mut tikz = Tikz::default();
+        tikz.output().update("./plot.tex");
+
+        let export = Render::export_commands(commands, &[&png, &tikz]).unwrap();
+
+        assert_eq!(export[2].to_string(), "set title '$\\alpha$'");
+        assert_eq!(export[6].to_string(), "set title '$\\alpha$' noenhanced");
+    }
+
+    #[test]
+    fn test_exporting_requires_an_output_for_every_terminal() {
+        let commands: VecDeque<GnuCommand> = vec![GnuCommand::new("plot sin(x)")].into();
+        let svg = Svg::default();
+
+        assert!(Render::export_commands(commands, &[&svg]).is_err());
+    }
+
+    #[test]
+    fn test_rendering_to_memory_writes_to_stdout() {
+        let commands: VecDeque<GnuCommand> = vec![
+            GnuCommand::new("set output './plot.png'"),
+            GnuCommand::new("set term pngcairo enhanced"),
+            GnuCommand::new("plot sin(x)"),
+        ].into();
+        let mut svg = Svg::default();
+        svg.output().update("./plot.svg");
+
+        let memory = Render::memory_commands(commands, &svg).unwrap();
+        let memory: Vec<String> = memory.iter().map(GnuCommand::to_string).collect();
+
+        assert_eq!(memory, vec![
+            "set term svg enhanced",
+            "set output",
+            "plot sin(x)",
+        ]);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_rendering_to_memory_does_not_require_an_output() {
+        let commands: VecDeque<GnuCommand> = vec![GnuCommand::new("plot sin(x)")].into();
+
+        let memory = Render::memory_commands(commands, &PdfCairo::default()).unwrap();
+
+        assert_eq!(memory[0].to_string(), "set term pdfcairo enhanced");
+        assert_eq!(memory[1].to_string(), "set output");
+    }
+
+    #[test]
+    fn test_rendering_to_memory_requires_a_terminal_writing_to_stdout() {
+        let commands: VecDeque<GnuCommand> = vec![GnuCommand::new("plot sin(x)")].into();
+
+        let result = Render::memory_commands(commands, &GifAnimate::default());
+
+        assert!(matches!(result, Err(RenderError::OutputNotCapturable("gif"))));
+    }
+
+    #[test]
+    fn test_checking_replaces_the_terminal_by_unknown() {
+        let commands: VecDeque<GnuCommand> = vec![
+            GnuCommand::new("set output './plot.png'"),
+            GnuCommand::new("set term pngcairo enhanced"),
+            GnuCommand::new("plot sin(x)"),
+        ].into();
+
+        let (check, origins) = Render::check_commands(commands);
+        let check: Vec<String> = check.iter().map(GnuCommand::to_string).collect();
+
+        assert_eq!(check, vec!["set terminal unknown", "plot sin(x)"]);
+        assert_eq!(origins, vec![None, Some(2)]);
+    }
+
+    /// `sh` reads its commands from STDIN like gnuplot does, so it reports messages as gnuplot
+    /// would. Lines 1 and 2 are `clear` and `set terminal unknown`.
+    #[test]
+    #[cfg(unix)]
+    fn test_checking_reports_warnings_along_with_the_error() {
+        let backend = GnuplotBackend::new().with_executable(Executable::new("sh"));
+        let commands: VecDeque<GnuCommand> = vec![
+            GnuCommand::new("set output './plot.png'"),
+            GnuCommand::new("echo 'line 3: warning: Skipping data file with no valid points' >&2"),
+            GnuCommand::new("echo 'line 4: undefined variable: foo' >&2; exit 1"),
+        ].into();
+
+        let diagnostics = Render::check(commands, &backend).unwrap();
+
+        assert_eq!(diagnostics.len(), 2);
+        assert!(diagnostics[0].is_warning());
+        assert_eq!(diagnostics[0].index, Some(1));
+        assert_eq!(diagnostics[1].message, "undefined variable: foo");
+        assert_eq!(diagnostics[1].index, Some(2));
+    }
+
+    #[test]
+    fn test_checking_reports_errors_as_diagnostics() {
+        struct Failing;
+
+        impl Backend for Failing {
+            fn render(&self, commands: VecDeque<GnuCommand>) -> RenderResult<Vec<GnuplotDiagnostic>> {
+                Err(RenderError::GnuplotError(GnuplotDiagnostic {
+                    line: Some(3),
+                    index: Some(1),
+                    command: commands.get(1).cloned(),
+                    message: "undefined variable: foo".into()
+                }))
+            }
+
+            fn render_to_bytes(&self, _: VecDeque<GnuCommand>) -> RenderResult<(Vec<u8>, Vec<GnuplotDiagnostic>)> {
+                Ok((vec![], vec![]))
+            }
+
+            fn render_frames(
+                &self,
+                _: VecDeque<GnuCommand>,
+                _: &mut dyn Iterator<Item = GnuCommandFactoryResult>
+            ) -> RenderResult<Vec<GnuplotDiagnostic>> {
+                Ok(vec![])
+            }
+        }
+
+        let commands: VecDeque<GnuCommand> = vec![GnuCommand::new("plot foo")].into();
+        let diagnostics = Render::check(commands, &Failing).unwrap();
+
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].command, Some(GnuCommand::new("plot foo")));
+    }
+}
@@ -0,0 +1,149 @@
+//! Rendering on a tokio runtime, so gnuplot can run without blocking an executor thread. Only
+//! available with the `tokio` feature.
+
+use std::collections::VecDeque;
+use std::process::Stdio;
+use tokio::io::AsyncWriteExt;
+use tokio::process::Command;
+use crate::prelude::*;
+use super::diagnostic::Transcript;
+
+impl Executable {
+    /// Fails when a command requires a newer version of gnuplot, like `check()`, but detects the
+    /// version using tokio's processes so the executor thread isn't blocked
+    async fn check_async(&self, commands: &VecDeque<GnuCommand>) -> RenderResult<()> {
+        if commands.iter().all(|command| command.required_version().is_none()) {
+            return Ok(());
+        }
+
+        let version = match self.detected_version() {
+            Some(version) => version,
+            None => {
+                let output = Command::from(self.command())
+                    .arg("--version")
+                    .output()
+                    .await
+                    .or(Err(RenderError::GnuSpawnFailed))?;
+                self.remember_version(&output.stdout)?
+            }
+        };
+        version.check(commands)?;
+
+        Ok(())
+    }
+}
+
+impl Render {
+    /// Renders the commands using tokio's processes, capturing STDOUT when `stdout` is piped
+    async fn run_async(
+        executable: &Executable,
+        commands: VecDeque<GnuCommand>,
+        stdout: Stdio
+    ) -> RenderResult<(Vec<u8>, Vec<GnuplotDiagnostic>)> {
+        executable.check_async(&commands).await?;
+
+        // the commands are small compared to the plot, so they are written out at once
+        let mut transcript = Transcript::new();
+        let mut script = vec![];
+        Render::write_commands(&mut script, &mut transcript, commands.into())?;
+
+        let mut gnu = Command::from(executable.command())
+            .arg("-p")
+            .stdin(Stdio::piped())
+            .stdout(stdout)
+            .stderr(Stdio::piped())
+            .kill_on_drop(true)
+            .spawn()
+            .or(Err(RenderError::GnuSpawnFailed))?;
+        let mut stdin = gnu.stdin.take().ok_or(RenderError::GnuSTDINNotAccessible)?;
+
+        // written by a separate task, so gnuplot can't block on a full STDOUT or STDERR pipe
+        // while we are still writing to its STDIN
+        let writer = tokio::spawn(async move {
+            stdin.write_all(&script).await.or(Err(RenderError::WritingCommandFailed))
+        });
+        let output = gnu.wait_with_output().await.or(Err(RenderError::WaitingForGnuPlotFailed))?;
+        let written = writer.await.or(Err(RenderError::WritingCommandFailed))?;
+
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let warnings = Render::conclude(&transcript, &stderr, output.status)?;
+        written?;
+
+        Ok((output.stdout, warnings))
+    }
+
+    /// Renders the commands without blocking the thread and returns the warnings gnuplot reported
+    pub async fn render_async(commands: VecDeque<GnuCommand>) -> RenderResult<Vec<GnuplotDiagnostic>> {
+        let (_, warnings) = Render::run_async(&Executable::default(), commands, Stdio::inherit()).await?;
+        Ok(warnings)
+    }
+
+    /// Renders the commands without blocking the thread, capturing everything gnuplot writes to
+    /// its STDOUT. Returns the output along with the warnings gnuplot reported.
+    pub async fn render_to_bytes_async(commands: VecDeque<GnuCommand>) -> RenderResult<(Vec<u8>, Vec<GnuplotDiagnostic>)> {
+        Render::run_async(&Executable::default(), commands, Stdio::piped()).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn runtime() -> tokio::runtime::Runtime {
+        tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .unwrap()
+    }
+
+    /// `sh` reads its commands from STDIN like gnuplot does
+    #[test]
+    #[cfg(unix)]
+    fn test_output_is_captured_asynchronously() {
+        let executable = Executable::new("sh");
+        let commands: VecDeque<GnuCommand> = vec![GnuCommand::new("echo plotted")].into();
+
+        let (output, warnings) = runtime()
+            .block_on(Render::run_async(&executable, commands, Stdio::piped()))
+            .unwrap();
+
+        assert!(String::from_utf8(output).unwrap().ends_with("plotted\n"));
+        assert!(warnings.is_empty());
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_an_unsuccessful_exit_fails_asynchronously() {
+        let executable = Executable::new("sh");
+        let commands: VecDeque<GnuCommand> = vec![GnuCommand::new("exit 3")].into();
+
+        let result = runtime().block_on(Render::run_async(&executable, commands, Stdio::piped()));
+
+        assert!(matches!(result, Err(RenderError::GnuplotExited { code: Some(3), .. })));
+    }
+
+    #[test]
+    fn test_a_missing_executable_fails_version_detection_asynchronously() {
+        let executable = Executable::new("/nonexistent/gnuplot");
+        let commands: VecDeque<GnuCommand> = vec![
+            GnuCommand::new("set term sixelgd").requires(Version::new(5, 2, 0))
+        ].into();
+
+        let result = runtime().block_on(executable.check_async(&commands));
+
+        assert!(matches!(result, Err(RenderError::GnuSpawnFailed)));
+    }
+
+    #[test]
+    fn test_a_detected_version_is_checked_asynchronously() {
+        let executable = Executable::new("/nonexistent/gnuplot-async-5.0");
+        executable.remember_version(b"gnuplot 5.0 patchlevel 7").unwrap();
+        let commands: VecDeque<GnuCommand> = vec![
+            GnuCommand::new("set term sixelgd").requires(Version::new(5, 2, 0))
+        ].into();
+
+        let result = runtime().block_on(executable.check_async(&commands));
+
+        assert!(matches!(result, Err(RenderError::CommandGenerationFailed(_))));
+    }
+}
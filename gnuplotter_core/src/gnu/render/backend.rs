@@ -0,0 +1,340 @@
+use std::collections::VecDeque;
+use std::fs;
+use std::process::Stdio;
+use std::time::Duration;
+use std::sync::{Arc, Mutex, RwLock};
+use crate::prelude::*;
+use crate::gnu::render::limits::Limits;
+
+/// Executes the commands of a plot. `CanRender` renders through a backend, which makes it possible
+/// to replace gnuplot, e.g. by a `RecordingBackend` in tests where gnuplot is not installed. Only
+/// its `_async` variants bypass the backend and always run gnuplot.
+pub trait Backend {
+    /// Renders the commands and returns the warnings reported while rendering
+    fn render(&self, commands: VecDeque<GnuCommand>) -> RenderResult<Vec<GnuplotDiagnostic>>;
+
+    /// Renders the commands and returns what was written to STDOUT along with the warnings
+    /// reported while rendering
+    fn render_to_bytes(&self, commands: VecDeque<GnuCommand>) -> RenderResult<(Vec<u8>, Vec<GnuplotDiagnostic>)>;
+
+    /// Renders the setup followed by each frame into a single output. A frame is only generated
+    /// once the previous frame finished rendering.
+    fn render_frames(
+        &self,
+        setup: VecDeque<GnuCommand>,
+        frames: &mut dyn Iterator<Item = GnuCommandFactoryResult>
+    ) -> RenderResult<Vec<GnuplotDiagnostic>>;
+
+    /// Renders the commands and returns every error and warning reported while rendering, instead
+    /// of failing on the first error. By default, only the error a render failed on is returned.
+    fn check(&self, commands: VecDeque<GnuCommand>) -> RenderResult<Vec<GnuplotDiagnostic>> {
+        match self.render(commands) {
+            Err(RenderError::GnuplotError(error)) => Ok(vec![error]),
+            result => result
+        }
+    }
+}
+
+/// Renders by running the gnuplot executable. This is the default backend, using the default
+/// `Executable` without a timeout.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct GnuplotBackend {
+    executable: Executable,
+    timeout: Option<Duration>,
+    cancellation: Option<Cancellation>
+}
+
+impl GnuplotBackend {
+    pub fn new() -> Self {
+        GnuplotBackend::default()
+    }
+
+    pub fn with_executable(mut self, executable: Executable) -> Self {
+        self.executable = executable;
+        self
+    }
+
+    /// Limits the time each render may take. When a render takes longer, gnuplot is killed and
+    /// `RenderError::TimedOut` is returned.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Allows renders to be cancelled from another thread, which kills gnuplot and returns
+    /// `RenderError::Cancelled`
+    pub fn with_cancellation(mut self, cancellation: Cancellation) -> Self {
+        self.cancellation = Some(cancellation);
+        self
+    }
+
+    pub fn executable(&self) -> &Executable {
+        &self.executable
+    }
+
+    fn limits(&self) -> Limits {
+        Limits::start(self.timeout, self.cancellation.clone())
+    }
+}
+
+impl Backend for GnuplotBackend {
+    fn render(&self, commands: VecDeque<GnuCommand>) -> RenderResult<Vec<GnuplotDiagnostic>> {
+        let (_, warnings) = Render::run(&self.executable, &self.limits(), commands, Stdio::inherit())?;
+        Ok(warnings)
+    }
+
+    fn render_to_bytes(&self, commands: VecDeque<GnuCommand>) -> RenderResult<(Vec<u8>, Vec<GnuplotDiagnostic>)> {
+        Render::run(&self.executable, &self.limits(), commands, Stdio::piped())
+    }
+
+    fn render_frames(
+        &self,
+        setup: VecDeque<GnuCommand>,
+        frames: &mut dyn Iterator<Item = GnuCommandFactoryResult>
+    ) -> RenderResult<Vec<GnuplotDiagnostic>> {
+        Render::render_frames(&self.executable, &self.limits(), setup, frames)
+    }
+
+    fn check(&self, commands: VecDeque<GnuCommand>) -> RenderResult<Vec<GnuplotDiagnostic>> {
+        Render::run_check(&self.executable, &self.limits(), commands)
+    }
+}
+
+/// Everything a `RecordingBackend` was asked to render at once
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Recording {
+    pub commands: Vec<GnuCommand>,
+    /// Content of the data files read by the commands, by path. Data is read as it is rendered,
+    /// so a file rewritten by every frame of an animation appears once per frame.
+    pub data: Vec<(String, String)>
+}
+
+impl Recording {
+    fn record(&mut self, commands: VecDeque<GnuCommand>) {
+        for command in commands {
+            for data in command.data() {
+                if let Ok(content) = fs::read_to_string(&data.path) {
+                    self.data.push((data.path.clone(), content));
+                }
+            }
+            self.commands.push(command);
+        }
+    }
+}
+
+/// Records the commands and data it is asked to render instead of rendering them, so tests can
+/// assert on them without gnuplot being installed. Rendering always succeeds without warnings or
+/// output.
+#[derive(Debug, Default)]
+pub struct RecordingBackend {
+    recordings: Mutex<Vec<Recording>>
+}
+
+impl RecordingBackend {
+    pub fn new() -> Self {
+        RecordingBackend::default()
+    }
+
+    pub fn recordings(&self) -> Vec<Recording> {
+        self.lock().clone()
+    }
+
+    /// The most recent recording, if anything was rendered
+    pub fn last(&self) -> Option<Recording> {
+        self.lock().last().cloned()
+    }
+
+    pub fn clear(&self) {
+        self.lock().clear();
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Vec<Recording>> {
+        // a test panicking while rendering leaves the recordings as they were
+        self.recordings.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    fn push(&self, recording: Recording) {
+        self.lock().push(recording);
+    }
+}
+
+impl Backend for RecordingBackend {
+    fn render(&self, commands: VecDeque<GnuCommand>) -> RenderResult<Vec<GnuplotDiagnostic>> {
+        let mut recording = Recording::default();
+        recording.record(commands);
+        self.push(recording);
+
+        Ok(vec![])
+    }
+
+    fn render_to_bytes(&self, commands: VecDeque<GnuCommand>) -> RenderResult<(Vec<u8>, Vec<GnuplotDiagnostic>)> {
+        Ok((vec![], self.render(commands)?))
+    }
+
+    fn render_frames(
+        &self,
+        setup: VecDeque<GnuCommand>,
+        frames: &mut dyn Iterator<Item = GnuCommandFactoryResult>
+    ) -> RenderResult<Vec<GnuplotDiagnostic>> {
+        let mut recording = Recording::default();
+        recording.record(setup);
+        for frame in frames {
+            recording.record(frame?);
+        }
+        self.push(recording);
+
+        Ok(vec![])
+    }
+}
+
+/// The backend set using `set_default_backend()`, gnuplot when none is set
+static DEFAULT_BACKEND: RwLock<Option<Arc<dyn Backend + Send + Sync>>> = RwLock::new(None);
+
+/// Replaces the backend `CanRender` uses. The backend is shared by every thread, so plots rendered
+/// from worker threads or tasks use it as well.
+pub fn set_default_backend(backend: Arc<dyn Backend + Send + Sync>) {
+    *DEFAULT_BACKEND.write().unwrap_or_else(|poisoned| poisoned.into_inner()) = Some(backend);
+}
+
+/// Restores gnuplot as the backend `CanRender` uses
+pub fn reset_default_backend() {
+    *DEFAULT_BACKEND.write().unwrap_or_else(|poisoned| poisoned.into_inner()) = None;
+}
+
+/// The backend `CanRender` uses
+pub fn default_backend() -> Arc<dyn Backend + Send + Sync> {
+    DEFAULT_BACKEND
+        .read()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .clone()
+        .unwrap_or_else(|| Arc::new(GnuplotBackend::default()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Sine;
+
+    impl GnuCommandFactory for Sine {
+        fn as_commands(&self) -> GnuCommandFactoryResult {
+            Ok(vec![GnuCommand::new("plot sin(x)")].into())
+        }
+    }
+
+    impl CanRender for Sine {}
+
+    #[test]
+    fn test_rendering_is_recorded_per_call() {
+        let backend = RecordingBackend::new();
+        Sine.render_with(&backend).unwrap();
+
+        assert_eq!(backend.recordings().len(), 1);
+        assert_eq!(backend.last().unwrap().commands, vec![GnuCommand::new("plot sin(x)")]);
+    }
+
+    /// The only test setting the default backend, as it is shared by every test in this crate
+    #[test]
+    fn test_rendering_is_recorded_by_the_default_backend() {
+        let backend = Arc::new(RecordingBackend::new());
+        set_default_backend(backend.clone());
+
+        Sine.render().unwrap();
+        Sine.render_animation(vec![Sine, Sine]).unwrap();
+        std::thread::spawn(|| Sine.render_to_bytes()).join().unwrap().unwrap();
+        reset_default_backend();
+
+        let recordings = backend.recordings();
+        assert_eq!(recordings.len(), 3);
+        assert_eq!(recordings[1].commands.len(), 3);
+    }
+
+    #[test]
+    fn test_rendering_variants_are_recorded_by_the_given_backend() {
+        let backend = RecordingBackend::new();
+        let mut svg = Svg::default();
+        svg.output().update("./plot.svg");
+
+        Sine.render_to_bytes_with(&backend).unwrap();
+        Sine.render_to_memory_with(&svg, &backend).unwrap();
+        Sine.export_with(&[&svg], &backend).unwrap();
+        Sine.check_with(&backend).unwrap();
+        Sine.render_animation_with(vec![Sine, Sine], &backend).unwrap();
+
+        let recordings = backend.recordings();
+        assert_eq!(recordings.len(), 5);
+        assert_eq!(recordings[3].commands[0], GnuCommand::new("set terminal unknown"));
+        assert_eq!(recordings[4].commands.len(), 3);
+    }
+
+    #[test]
+    fn test_data_files_are_recorded() {
+        let (data_file, mut file) = DataWorkspace::default().allocate("backend").unwrap();
+        std::io::Write::write_all(&mut file, b"0\t4\n").unwrap();
+        let path = data_file.path().display().to_string();
+
+        let backend = RecordingBackend::new();
+        backend.render(vec![
+            GnuCommand::new("set output './plot.png'"),
+            GnuCommand::new(format!("set title '{}'", path)),
+            GnuCommand::new(format!("plot \"{}\" using 1:2", path)).reads(&path)
+        ].into()).unwrap();
+
+        let recording = backend.last().unwrap();
+        assert_eq!(recording.data, vec![(path, "0\t4\n".to_string())]);
+    }
+
+    /// `sh` reads its commands from STDIN like gnuplot does, which makes it hang on `sleep`
+    #[cfg(unix)]
+    fn hanging_commands() -> VecDeque<GnuCommand> {
+        vec![GnuCommand::new("sleep 5")].into()
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_warnings_are_returned_along_with_the_output() {
+        let backend = GnuplotBackend::new().with_executable(Executable::new("sh"));
+        let commands: VecDeque<GnuCommand> = vec![
+            GnuCommand::new("echo plotted; echo 'line 2: warning: Skipping data file with no valid points' >&2")
+        ].into();
+
+        let (output, warnings) = backend.render_to_bytes(commands).unwrap();
+
+        assert!(String::from_utf8(output).unwrap().ends_with("plotted\n"));
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].index, Some(0));
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_rendering_times_out() {
+        let backend = GnuplotBackend::new()
+            .with_executable(Executable::new("sh"))
+            .with_timeout(Duration::from_millis(100));
+
+        let started = std::time::Instant::now();
+        let result = backend.render_to_bytes(hanging_commands());
+
+        assert!(matches!(result, Err(RenderError::TimedOut)));
+        assert!(started.elapsed() < Duration::from_secs(5));
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_rendering_is_cancelled_from_another_thread() {
+        let cancellation = Cancellation::new();
+        let backend = GnuplotBackend::new()
+            .with_executable(Executable::new("sh"))
+            .with_cancellation(cancellation.clone());
+
+        let canceller = std::thread::spawn(move || {
+            std::thread::sleep(Duration::from_millis(100));
+            cancellation.cancel();
+        });
+        let result = backend.render_to_bytes(hanging_commands());
+        canceller.join().unwrap();
+
+        assert!(matches!(result, Err(RenderError::Cancelled)));
+        assert!(matches!(backend.render_to_bytes(hanging_commands()), Err(RenderError::Cancelled)));
+    }
+}
@@ -0,0 +1,192 @@
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+use std::time::{Duration, Instant};
+use crate::prelude::*;
+use crate::gnu::script::inline_data;
+
+/// The outcome of rendering a single plot of a batch
+#[derive(Debug)]
+pub struct BatchResult {
+    /// Position of the plot in the batch
+    pub index: usize,
+    pub result: RenderResult<Vec<GnuplotDiagnostic>>,
+    pub elapsed: Duration
+}
+
+/// The outcome of rendering a batch, with a result for every plot in the order they were given
+#[derive(Debug)]
+pub struct BatchReport {
+    pub results: Vec<BatchResult>,
+    pub elapsed: Duration
+}
+
+impl BatchReport {
+    pub fn succeeded(&self) -> usize {
+        self.results.iter().filter(|plot| plot.result.is_ok()).count()
+    }
+
+    pub fn failed(&self) -> usize {
+        self.results.len() - self.succeeded()
+    }
+}
+
+/// Renders many plots concurrently. Every worker renders one plot at a time, so no more than
+/// `workers` gnuplot processes run at once. Use a `GnuplotBackend` with a timeout or cancellation
+/// to limit the time a batch may take.
+///
+/// Plots write their data files while generating their commands, concurrently with the other
+/// plots. Plots storing series data using `SeriesStorage::File` must therefore not share a file
+/// within a batch, while `SeriesStorage::Inline` and `SeriesStorage::Workspace` are always safe.
+pub struct Batch {
+    workers: usize,
+    backend: Arc<dyn Backend + Send + Sync>
+}
+
+impl Default for Batch {
+    fn default() -> Self {
+        Batch {
+            workers: thread::available_parallelism().map(|workers| workers.get()).unwrap_or(1),
+            backend: default_backend()
+        }
+    }
+}
+
+impl Batch {
+    /// A batch using as many workers as there are CPUs, rendering with the default backend
+    pub fn new() -> Self {
+        Batch::default()
+    }
+
+    pub fn with_workers(mut self, workers: usize) -> Self {
+        self.workers = workers.max(1);
+        self
+    }
+
+    pub fn with_backend(mut self, backend: Arc<dyn Backend + Send + Sync>) -> Self {
+        self.backend = backend;
+        self
+    }
+
+    /// Generates the commands of a plot with its data inlined, so they no longer depend on data
+    /// files that a concurrent job may overwrite.
+    fn commands<P: GnuCommandFactory + ?Sized>(plot: &P) -> RenderResult<VecDeque<GnuCommand>> {
+        Ok(inline_data(plot.as_commands()?)?)
+    }
+
+    fn render_one(&self, index: usize, plot: &(dyn CanRender + Sync)) -> BatchResult {
+        let started = Instant::now();
+        let result = Batch::commands(plot).and_then(|commands| self.backend.render(commands));
+
+        BatchResult {
+            index,
+            result,
+            elapsed: started.elapsed()
+        }
+    }
+
+    /// Renders every plot and reports how each of them went. A failing plot does not stop the
+    /// others from rendering. Plots of different types can be rendered in the same batch.
+    pub fn render(&self, plots: &[&(dyn CanRender + Sync)]) -> BatchReport {
+        let started = Instant::now();
+        let next = AtomicUsize::new(0);
+        let results = Mutex::new(Vec::with_capacity(plots.len()));
+
+        thread::scope(|scope| {
+            for _ in 0..self.workers.min(plots.len()) {
+                scope.spawn(|| {
+                    loop {
+                        let index = next.fetch_add(1, Ordering::SeqCst);
+                        let Some(&plot) = plots.get(index) else { break };
+
+                        let result = self.render_one(index, plot);
+                        results.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).push(result);
+                    }
+                });
+            }
+        });
+
+        let mut results = results.into_inner().unwrap_or_else(|poisoned| poisoned.into_inner());
+        results.sort_by_key(|result| result.index);
+
+        BatchReport {
+            results,
+            elapsed: started.elapsed()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Numbered(usize);
+
+    impl GnuCommandFactory for Numbered {
+        fn as_commands(&self) -> GnuCommandFactoryResult {
+            if self.0 == 3 {
+                return Err(GnuCommandFactoryError::required_value_missing("no plot for 3"));
+            }
+            Ok(vec![GnuCommand::new(format!("plot {}", self.0))].into())
+        }
+    }
+
+    impl CanRender for Numbered {}
+
+    struct Sine;
+
+    impl GnuCommandFactory for Sine {
+        fn as_commands(&self) -> GnuCommandFactoryResult {
+            Ok(vec![GnuCommand::new("plot sin(x)")].into())
+        }
+    }
+
+    impl CanRender for Sine {}
+
+    #[test]
+    fn test_every_plot_is_reported_in_order() {
+        let backend = Arc::new(RecordingBackend::new());
+        let plots: Vec<Numbered> = (0..10).map(Numbered).collect();
+        let plots: Vec<&(dyn CanRender + Sync)> = plots.iter().map(|plot| plot as _).collect();
+
+        let report = Batch::new()
+            .with_workers(3)
+            .with_backend(backend.clone())
+            .render(&plots);
+
+        assert_eq!(report.results.len(), 10);
+        assert!(report.results.iter().enumerate().all(|(index, result)| result.index == index));
+        assert_eq!(report.succeeded(), 9);
+        assert_eq!(report.failed(), 1);
+        assert!(matches!(report.results[3].result, Err(RenderError::CommandGenerationFailed(_))));
+        assert_eq!(backend.recordings().len(), 9);
+    }
+
+    #[test]
+    fn test_series_data_is_inlined() {
+        let mut series = Series::<f64>::new();
+        let mut serie = Serie::with_title("A");
+        serie.add(4.0);
+        series.add(serie);
+        let file = std::env::temp_dir().join("gnuplotter_batch_series_data.txt");
+        series.storage().update(SeriesStorage::File(file.to_string_lossy().into()));
+
+        let commands = Batch::commands(&series).unwrap();
+
+        assert!(commands[0].to_string().starts_with("$DATA_1 << EOD\n"));
+        assert!(commands[1].to_string().starts_with("plot $DATA_1 using 1:2"));
+    }
+
+    #[test]
+    fn test_plots_of_different_types_are_rendered_together() {
+        let backend = Arc::new(RecordingBackend::new());
+
+        let report = Batch::new()
+            .with_backend(backend.clone())
+            .render(&[&Numbered(1), &Sine]);
+
+        assert_eq!(report.succeeded(), 2);
+        assert_eq!(backend.recordings().len(), 2);
+    }
+}
@@ -0,0 +1,218 @@
+use std::fmt::{Display, Formatter};
+use std::io::Write;
+use crate::prelude::*;
+
+/// An error or warning reported by gnuplot. When gnuplot mentions the line it was reading, the
+/// diagnostic points back to the command that was sent on that line and its index in the queue.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GnuplotDiagnostic {
+    pub line: Option<usize>,
+    pub index: Option<usize>,
+    pub command: Option<GnuCommand>,
+    pub message: String
+}
+
+impl GnuplotDiagnostic {
+    pub fn is_warning(&self) -> bool {
+        self.message.to_lowercase().starts_with("warning")
+    }
+}
+
+impl Display for GnuplotDiagnostic {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match (&self.index, &self.command) {
+            (Some(index), Some(command)) =>
+                f.write_fmt(format_args!("{} (command {}: `{}`)", self.message, index, command)),
+            _ => f.write_str(&self.message)
+        }
+    }
+}
+
+/// Keeps track of every line sent to gnuplot, so the line numbers gnuplot reports can be traced
+/// back to the commands they belong to. Lines gnuplotter sends for its own purposes, such as
+/// `clear` and `exit`, don't belong to a command.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct Transcript {
+    skipped: usize,
+    lines: Vec<Option<usize>>,
+    commands: Vec<GnuCommand>
+}
+
+impl Transcript {
+    pub(crate) fn new() -> Self {
+        Transcript::default()
+    }
+
+    /// Starts a new queue of commands on the same gnuplot process. Gnuplot keeps counting lines,
+    /// while diagnostics point to the commands of the new queue only.
+    pub(crate) fn start_queue(&mut self) {
+        self.skipped += self.lines.len();
+        self.lines.clear();
+        self.commands.clear();
+    }
+
+    /// Writes a command from the queue
+    pub(crate) fn write_command<W: Write>(&mut self, writer: &mut W, command: GnuCommand) -> RenderResult<()> {
+        writeln!(writer, "{}", command).or(Err(RenderError::WritingCommandFailed))?;
+
+        let index = self.commands.len();
+        for _ in command.to_string().lines() {
+            self.lines.push(Some(index));
+        }
+        self.commands.push(command);
+
+        Ok(())
+    }
+
+    /// Writes a line that is not part of the queue
+    pub(crate) fn write_line<W: Write>(&mut self, writer: &mut W, line: &str) -> RenderResult<()> {
+        writeln!(writer, "{}", line).or(Err(RenderError::WritingCommandFailed))?;
+        self.lines.push(None);
+
+        Ok(())
+    }
+
+    /// Parses everything gnuplot wrote to its STDERR into diagnostics
+    pub(crate) fn diagnose(&self, stderr: &str) -> Vec<GnuplotDiagnostic> {
+        stderr
+            .lines()
+            .filter_map(parse_diagnostic)
+            .map(|(line, message)| {
+                let index = line
+                    .and_then(|line| line.checked_sub(self.skipped + 1))
+                    .and_then(|line| self.lines.get(line).cloned().flatten());
+                GnuplotDiagnostic {
+                    line,
+                    index,
+                    command: index.map(|index| self.commands[index].clone()),
+                    message
+                }
+            })
+            .collect()
+    }
+}
+
+/// Recognizes gnuplot messages such as `         line 3: undefined variable: foo`,
+/// `"<stdin>" line 3: undefined variable: foo` or `warning: Skipping data file with no valid points`.
+/// Other output, such as gnuplot echoing the faulty command, is ignored.
+fn parse_diagnostic(output: &str) -> Option<(Option<usize>, String)> {
+    let output = output.trim();
+
+    if let Some((number, message)) = parse_line(output) {
+        return Some((Some(number), message.to_string()));
+    }
+
+    if output.to_lowercase().starts_with("warning:") {
+        return Some((None, output.to_string()));
+    }
+
+    None
+}
+
+/// Splits the line number gnuplot puts in front of a message, optionally preceded by the quoted
+/// name of the input it was reading, from the message
+fn parse_line(output: &str) -> Option<(usize, &str)> {
+    let output = match output.strip_prefix('"') {
+        Some(quoted) => quoted.split_once('"')?.1.trim_start(),
+        None => output
+    };
+    let (number, message) = output.strip_prefix("line ")?.split_once(':')?;
+
+    Some((number.trim().parse().ok()?, message.trim()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn transcript() -> Transcript {
+        let mut transcript = Transcript::new();
+        let mut sink = vec![];
+        transcript.write_line(&mut sink, "clear").unwrap();
+        transcript.write_command(&mut sink, GnuCommand::new("set title 'diagnostics'")).unwrap();
+        transcript.write_command(&mut sink, GnuCommand::new("plot foo")).unwrap();
+        transcript.write_line(&mut sink, "exit").unwrap();
+
+        transcript
+    }
+
+    #[test]
+    fn test_errors_point_back_to_their_command() {
+        let stderr = "\ngnuplot> plot foo\n              ^\n         line 3: undefined variable: foo\n\n";
+        let diagnostics = transcript().diagnose(stderr);
+
+        assert_eq!(diagnostics, vec![GnuplotDiagnostic {
+            line: Some(3),
+            index: Some(1),
+            command: Some(GnuCommand::new("plot foo")),
+            message: "undefined variable: foo".into()
+        }]);
+        assert!(!diagnostics[0].is_warning());
+    }
+
+    #[test]
+    fn test_warnings_are_recognized() {
+        let stderr = "         line 3: warning: Skipping data file with no valid points\nwarning: empty x range\n";
+        let diagnostics = transcript().diagnose(stderr);
+
+        assert_eq!(diagnostics.len(), 2);
+        assert!(diagnostics.iter().all(GnuplotDiagnostic::is_warning));
+        assert_eq!(diagnostics[0].index, Some(1));
+        assert_eq!(diagnostics[1].line, None);
+        assert_eq!(diagnostics[1].command, None);
+    }
+
+    #[test]
+    fn test_errors_naming_their_input_point_back_to_their_command() {
+        let diagnostics = transcript().diagnose("\"<stdin>\" line 3: undefined variable: foo");
+
+        assert_eq!(diagnostics[0].line, Some(3));
+        assert_eq!(diagnostics[0].command, Some(GnuCommand::new("plot foo")));
+        assert_eq!(diagnostics[0].message, "undefined variable: foo");
+    }
+
+    #[test]
+    fn test_messages_merely_mentioning_a_line_are_not_located() {
+        let stderr = "Skipping the line 2: it has no valid points\nwarning: data on line 3: was ignored";
+        let diagnostics = transcript().diagnose(stderr);
+
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].line, None);
+        assert_eq!(diagnostics[0].message, "warning: data on line 3: was ignored");
+    }
+
+    #[test]
+    fn test_lines_outside_of_the_queue_have_no_command() {
+        let diagnostics = transcript().diagnose("line 4: unexpected end of input");
+
+        assert_eq!(diagnostics[0].index, None);
+        assert_eq!(diagnostics[0].command, None);
+    }
+
+    #[test]
+    fn test_diagnostics_point_to_the_current_queue() {
+        let mut transcript = transcript();
+        let mut sink = vec![];
+        transcript.start_queue();
+        transcript.write_line(&mut sink, "reset").unwrap();
+        transcript.write_command(&mut sink, GnuCommand::new("plot bar")).unwrap();
+
+        let diagnostics = transcript.diagnose("line 6: undefined variable: bar\nline 3: undefined variable: foo");
+
+        assert_eq!(diagnostics[0].index, Some(0));
+        assert_eq!(diagnostics[0].command, Some(GnuCommand::new("plot bar")));
+        assert_eq!(diagnostics[1].command, None);
+    }
+
+    #[test]
+    fn test_multiline_commands_span_several_lines() {
+        let mut transcript = Transcript::new();
+        let mut sink = vec![];
+        transcript.write_command(&mut sink, GnuCommand::new("$DATA << EOD\n1 2\nEOD")).unwrap();
+        transcript.write_command(&mut sink, GnuCommand::new("plot $DATA using 1:3")).unwrap();
+
+        let diagnostics = transcript.diagnose("line 4: warning: Skipping data file with no valid points");
+
+        assert_eq!(diagnostics[0].index, Some(1));
+    }
+}
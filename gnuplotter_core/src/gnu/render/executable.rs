@@ -0,0 +1,136 @@
+use std::collections::{HashMap, VecDeque};
+use std::env;
+use std::process::Command;
+use std::sync::{Mutex, OnceLock};
+use crate::prelude::*;
+
+/// Versions detected so far by the path of their executable, so every executable is only asked
+/// for its version once, however many `Executable`s refer to it
+static VERSIONS: OnceLock<Mutex<HashMap<String, Version>>> = OnceLock::new();
+
+fn versions() -> std::sync::MutexGuard<'static, HashMap<String, Version>> {
+    VERSIONS
+        .get_or_init(Default::default)
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+/// The gnuplot executable plots are rendered with. By default, this is the path in the `GNUPLOT`
+/// environment variable, or `gnuplot` found on the PATH otherwise.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Executable {
+    path: String
+}
+
+impl Default for Executable {
+    fn default() -> Self {
+        let path = env::var("GNUPLOT")
+            .ok()
+            .filter(|path| !path.trim().is_empty())
+            .unwrap_or_else(|| "gnuplot".into());
+
+        Executable::new(&path)
+    }
+}
+
+impl Executable {
+    pub fn new(path: &str) -> Self {
+        Executable {
+            path: path.into()
+        }
+    }
+
+    pub fn update(&mut self, path: &str) {
+        *self = Executable::new(path);
+    }
+
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    /// Detects the version by running `gnuplot --version`. It is only detected once per path for
+    /// the whole process.
+    pub fn version(&self) -> RenderResult<Version> {
+        if let Some(version) = self.detected_version() {
+            return Ok(version);
+        }
+
+        let output = Command::new(&self.path)
+            .arg("--version")
+            .output()
+            .or(Err(RenderError::GnuSpawnFailed))?;
+
+        self.remember_version(&output.stdout)
+    }
+
+    pub(crate) fn detected_version(&self) -> Option<Version> {
+        versions().get(&self.path).copied()
+    }
+
+    /// Parses the output of `gnuplot --version` and remembers the version for the path
+    pub(crate) fn remember_version(&self, output: &[u8]) -> RenderResult<Version> {
+        let output = String::from_utf8_lossy(output);
+        let version = Version::parse(&output)
+            .ok_or_else(|| RenderError::VersionUnknown(output.trim().into()))?;
+
+        Ok(*versions().entry(self.path.clone()).or_insert(version))
+    }
+
+    /// Fails when a command requires a newer version of gnuplot. The version is only detected
+    /// when a command requires one.
+    pub fn check(&self, commands: &VecDeque<GnuCommand>) -> RenderResult<()> {
+        if commands.iter().all(|command| command.required_version().is_none()) {
+            return Ok(());
+        }
+
+        self.version()?.check(commands)?;
+
+        Ok(())
+    }
+
+    pub(crate) fn command(&self) -> Command {
+        Command::new(&self.path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_commands_without_requirements_skip_version_detection() {
+        let executable = Executable::new("/nonexistent/gnuplot");
+        let commands: VecDeque<GnuCommand> = vec![GnuCommand::new("plot sin(x)")].into();
+
+        assert!(executable.check(&commands).is_ok());
+    }
+
+    #[test]
+    fn test_a_missing_executable_fails_version_detection() {
+        let executable = Executable::new("/nonexistent/gnuplot");
+        let commands: VecDeque<GnuCommand> = vec![
+            GnuCommand::new("set term sixelgd").requires(Version::new(5, 2, 0))
+        ].into();
+
+        assert!(matches!(executable.check(&commands), Err(RenderError::GnuSpawnFailed)));
+    }
+
+    #[test]
+    fn test_a_detected_version_is_checked() {
+        let executable = Executable::new("/nonexistent/gnuplot-5.0");
+        executable.remember_version(b"gnuplot 5.0 patchlevel 7").unwrap();
+        let commands: VecDeque<GnuCommand> = vec![
+            GnuCommand::new("set term sixelgd").requires(Version::new(5, 2, 0))
+        ].into();
+
+        assert!(matches!(executable.check(&commands), Err(RenderError::CommandGenerationFailed(_))));
+    }
+
+    #[test]
+    fn test_a_version_is_detected_once_per_path() {
+        Executable::new("/nonexistent/gnuplot-5.4").remember_version(b"gnuplot 5.4 patchlevel 2").unwrap();
+
+        assert_eq!(Executable::new("/nonexistent/gnuplot-5.4").version().unwrap(), Version::new(5, 4, 2));
+        assert_eq!(Executable::new("/nonexistent/gnuplot-6.0").detected_version(), None);
+    }
+}
@@ -0,0 +1,161 @@
+use std::io::{BufRead, BufReader, Read};
+use std::process::{Child, ExitStatus};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{channel, Receiver, RecvTimeoutError};
+use std::thread;
+use std::time::{Duration, Instant};
+use crate::prelude::*;
+
+/// How often a render checks whether it timed out or was cancelled while waiting for gnuplot
+const POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+/// Cancels the renders it is given to from another thread, e.g. to stop a long-running batch.
+/// Clones share the same state, so cancelling one of them cancels them all.
+#[derive(Debug, Clone, Default)]
+pub struct Cancellation(Arc<AtomicBool>);
+
+impl Cancellation {
+    pub fn new() -> Self {
+        Cancellation::default()
+    }
+
+    /// Stops every render using this cancellation, killing their gnuplot processes. Renders that
+    /// have yet to start fail immediately.
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::SeqCst);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::SeqCst)
+    }
+}
+
+impl PartialEq for Cancellation {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+/// The time a single render may take and the cancellation that may stop it
+#[derive(Debug, Clone, Default)]
+pub(crate) struct Limits {
+    deadline: Option<Instant>,
+    cancellation: Option<Cancellation>
+}
+
+impl Limits {
+    /// Starts counting the timeout from now
+    pub(crate) fn start(timeout: Option<Duration>, cancellation: Option<Cancellation>) -> Self {
+        Limits {
+            deadline: timeout.map(|timeout| Instant::now() + timeout),
+            cancellation
+        }
+    }
+
+    pub(crate) fn check(&self) -> RenderResult<()> {
+        if self.cancellation.as_ref().is_some_and(Cancellation::is_cancelled) {
+            return Err(RenderError::Cancelled);
+        }
+        if self.deadline.is_some_and(|deadline| Instant::now() >= deadline) {
+            return Err(RenderError::TimedOut);
+        }
+
+        Ok(())
+    }
+
+    /// Waits for gnuplot to exit, killing it when the limits are exceeded
+    pub(crate) fn wait(&self, child: &mut Child) -> RenderResult<ExitStatus> {
+        loop {
+            if let Some(status) = child.try_wait().or(Err(RenderError::WaitingForGnuPlotFailed))? {
+                return Ok(status);
+            }
+            if let Err(error) = self.check() {
+                kill(child);
+                return Err(error);
+            }
+            thread::sleep(POLL_INTERVAL);
+        }
+    }
+}
+
+/// Stops gnuplot without waiting for it to finish
+pub(crate) fn kill(child: &mut Child) {
+    let _ = child.kill();
+    let _ = child.wait();
+}
+
+/// Lines gnuplot writes to its STDERR. They are read on a separate thread, so waiting for a line
+/// can be limited in time.
+pub(crate) struct StderrLines {
+    lines: Receiver<String>
+}
+
+impl StderrLines {
+    pub(crate) fn new<R: Read + Send + 'static>(stderr: R) -> Self {
+        let (sender, lines) = channel();
+        thread::spawn(move || {
+            let mut stderr = BufReader::new(stderr);
+            let mut line = String::new();
+            while matches!(stderr.read_line(&mut line), Ok(read) if read > 0) {
+                if sender.send(std::mem::take(&mut line)).is_err() {
+                    break;
+                }
+            }
+        });
+
+        StderrLines { lines }
+    }
+
+    /// Waits for the next line, including its line break. Returns `None` once gnuplot closed its
+    /// STDERR.
+    pub(crate) fn next(&self, limits: &Limits) -> RenderResult<Option<String>> {
+        loop {
+            limits.check()?;
+            match self.lines.recv_timeout(POLL_INTERVAL) {
+                Ok(line) => return Ok(Some(line)),
+                Err(RecvTimeoutError::Timeout) => continue,
+                Err(RecvTimeoutError::Disconnected) => return Ok(None)
+            }
+        }
+    }
+
+    /// Collects the remaining lines once gnuplot exited
+    pub(crate) fn rest(&self) -> String {
+        self.lines.iter().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_limits_expire() {
+        assert!(Limits::default().check().is_ok());
+        assert!(matches!(Limits::start(Some(Duration::ZERO), None).check(), Err(RenderError::TimedOut)));
+        assert!(Limits::start(Some(Duration::from_secs(60)), None).check().is_ok());
+    }
+
+    #[test]
+    fn test_cancelling_is_shared_by_clones() {
+        let cancellation = Cancellation::new();
+        let limits = Limits::start(None, Some(cancellation.clone()));
+        assert!(limits.check().is_ok());
+
+        cancellation.clone().cancel();
+
+        assert!(matches!(limits.check(), Err(RenderError::Cancelled)));
+    }
+
+    #[test]
+    fn test_waiting_for_stderr_lines_is_limited() {
+        let stderr = StderrLines::new("first\nsecond\n".as_bytes());
+        let limits = Limits::default();
+
+        assert_eq!(stderr.next(&limits).unwrap(), Some("first\n".to_string()));
+        assert_eq!(stderr.rest(), "second\n");
+        assert_eq!(stderr.next(&limits).unwrap(), None);
+        assert!(matches!(stderr.next(&Limits::start(Some(Duration::ZERO), None)), Err(RenderError::TimedOut)));
+    }
+}
@@ -0,0 +1,241 @@
+use std::collections::VecDeque;
+use std::process::{Child, ChildStdin, Stdio};
+use std::time::Duration;
+use crate::prelude::*;
+use super::Render;
+use super::diagnostic::Transcript;
+use super::limits::{kill, Limits, StderrLines};
+
+/// A running gnuplot process along with everything sent to it so far
+struct Process {
+    child: Child,
+    stdin: ChildStdin,
+    stderr: StderrLines,
+    transcript: Transcript
+}
+
+impl Process {
+    fn spawn(executable: &Executable) -> RenderResult<Self> {
+        let mut child = Render::spawn(executable, Stdio::inherit(), Stdio::piped())?;
+        let stdin = child.stdin.take().ok_or(RenderError::GnuSTDINNotAccessible)?;
+        let stderr = child.stderr.take().ok_or(RenderError::SynchronizationFailed)?;
+
+        Ok(Process {
+            child,
+            stdin,
+            stderr: StderrLines::new(stderr),
+            transcript: Transcript::new()
+        })
+    }
+
+    /// Sends a plot, preceded by a `reset` so no settings leak from the previous plot, and waits
+    /// until gnuplot finished it. Unsetting the output closes the file the plot was written to.
+    fn render(&mut self, commands: VecDeque<GnuCommand>, limits: &Limits, output: &mut String) -> RenderResult<()> {
+        self.transcript.start_queue();
+        self.transcript.write_line(&mut self.stdin, "reset")?;
+        for command in commands {
+            self.transcript.write_command(&mut self.stdin, command)?;
+        }
+        self.transcript.write_line(&mut self.stdin, "unset output")?;
+
+        Render::synchronize(&mut self.stdin, &self.stderr, limits, &mut self.transcript, output)
+    }
+
+    /// Closes gnuplot's STDIN and waits for it to exit
+    fn exit(self, mut output: String) -> RenderResult<Vec<GnuplotDiagnostic>> {
+        let Process { mut child, mut stdin, stderr, mut transcript } = self;
+        let _ = transcript.write_line(&mut stdin, "exit");
+        drop(stdin);
+
+        output.push_str(&stderr.rest());
+        let status = child.wait().or(Err(RenderError::WaitingForGnuPlotFailed))?;
+        Render::conclude(&transcript, &output, status)
+    }
+}
+
+/// Keeps a single gnuplot process alive to render many plots, instead of starting gnuplot for
+/// every plot. Settings are reset between plots. When gnuplot stops because of an error in a plot,
+/// the next plot starts a new gnuplot process.
+pub struct GnuplotSession {
+    executable: Executable,
+    timeout: Option<Duration>,
+    cancellation: Option<Cancellation>,
+    process: Option<Process>
+}
+
+impl GnuplotSession {
+    pub fn new() -> RenderResult<Self> {
+        GnuplotSession::with_executable(Executable::default())
+    }
+
+    pub fn with_executable(executable: Executable) -> RenderResult<Self> {
+        let process = Process::spawn(&executable)?;
+
+        Ok(GnuplotSession {
+            executable,
+            timeout: None,
+            cancellation: None,
+            process: Some(process)
+        })
+    }
+
+    /// Limits the time each plot may take. When a plot takes longer, gnuplot is killed and the
+    /// next plot starts a new gnuplot process.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Allows plots to be cancelled from another thread, which kills gnuplot
+    pub fn with_cancellation(mut self, cancellation: Cancellation) -> Self {
+        self.cancellation = Some(cancellation);
+        self
+    }
+
+    /// Renders the commands and returns the warnings gnuplot reported once it finished
+    pub fn render(&mut self, commands: VecDeque<GnuCommand>) -> RenderResult<Vec<GnuplotDiagnostic>> {
+        let limits = Limits::start(self.timeout, self.cancellation.clone());
+        limits.check()?;
+        self.executable.check(&commands)?;
+        let mut process = match self.process.take() {
+            Some(process) => process,
+            None => Process::spawn(&self.executable)?
+        };
+
+        let mut output = String::new();
+        match process.render(commands, &limits, &mut output) {
+            Ok(()) => {
+                let warnings = Render::diagnose(&process.transcript, &output);
+                self.process = Some(process);
+                warnings
+            },
+            Err(error @ (RenderError::TimedOut | RenderError::Cancelled)) => {
+                kill(&mut process.child);
+                Err(error)
+            },
+            // gnuplot stopped, so whatever it reported before exiting explains why
+            Err(error) => {
+                process.exit(output)?;
+                Err(error)
+            }
+        }
+    }
+
+    /// Stops gnuplot, reporting an unsuccessful exit. Dropping the session stops gnuplot as well,
+    /// while ignoring the outcome.
+    pub fn close(mut self) -> RenderResult<()> {
+        match self.process.take() {
+            Some(process) => process.exit(String::new()).map(|_| ()),
+            None => Ok(())
+        }
+    }
+}
+
+impl Drop for GnuplotSession {
+    fn drop(&mut self) {
+        if let Some(process) = self.process.take() {
+            let _ = process.exit(String::new());
+        }
+    }
+}
+
+#[cfg(test)]
+#[cfg(unix)]
+mod tests {
+    use std::fs;
+    use std::os::unix::fs::PermissionsExt;
+    use std::path::PathBuf;
+    use crate::gnu::render::SYNCHRONIZATION_MARKER;
+    use super::*;
+
+    /// Writes a script standing in for gnuplot, which logs every line it reads. It prints the
+    /// synchronization marker unless `print` was redirected, and stops at `fail` like gnuplot stops
+    /// at an error.
+    fn stub(name: &str) -> (Executable, PathBuf) {
+        let script = std::env::temp_dir().join(format!("gnuplotter_session_{}.sh", name));
+        let log = std::env::temp_dir().join(format!("gnuplotter_session_{}.log", name));
+        let _ = fs::remove_file(&log);
+        fs::write(&script, format!(r#"#!/bin/sh
+log='{}'
+echo started >> "$log"
+line=0
+printing=stderr
+while IFS= read -r command; do
+    line=$((line + 1))
+    echo "$command" >> "$log"
+    case "$command" in
+        'set print') printing=stderr ;;
+        'set print '*) printing=file ;;
+        'print "{}"') if [ "$printing" = stderr ]; then echo '{1}' >&2; fi ;;
+        warn) echo "line $line: warning: plot warned" >&2 ;;
+        fail) echo "line $line: plot failed" >&2; exit 1 ;;
+        exit) exit 0 ;;
+    esac
+done
+"#, log.display(), SYNCHRONIZATION_MARKER)).unwrap();
+        fs::set_permissions(&script, fs::Permissions::from_mode(0o755)).unwrap();
+
+        (Executable::new(&script.to_string_lossy()), log)
+    }
+
+    /// Starts a session, retrying while another test still holds the just written script open
+    fn session(executable: &Executable) -> GnuplotSession {
+        for _ in 0..10 {
+            if let Ok(session) = GnuplotSession::with_executable(executable.clone()) {
+                return session.with_timeout(Duration::from_secs(5));
+            }
+            std::thread::sleep(Duration::from_millis(50));
+        }
+        panic!("unable to start the stub executable");
+    }
+
+    fn log(path: &PathBuf) -> Vec<String> {
+        fs::read_to_string(path).unwrap().lines().map(String::from).collect()
+    }
+
+    #[test]
+    fn test_plots_are_reset_and_synchronized() {
+        let (executable, log_file) = stub("synchronized");
+        let mut session = session(&executable);
+
+        let first = session.render(vec![GnuCommand::new("plot 1")].into()).unwrap();
+        let second = session.render(vec![GnuCommand::new("warn")].into()).unwrap();
+        session.close().unwrap();
+
+        assert!(first.is_empty());
+        assert_eq!(second.len(), 1);
+        assert_eq!(second[0].index, Some(0));
+        let marker = format!("print \"{}\"", SYNCHRONIZATION_MARKER);
+        assert_eq!(log(&log_file), vec![
+            "started",
+            "reset", "plot 1", "unset output", "set print", &marker,
+            "reset", "warn", "unset output", "set print", &marker,
+            "exit"
+        ]);
+    }
+
+    #[test]
+    fn test_a_new_process_starts_after_an_error() {
+        let (executable, log_file) = stub("restarted");
+        let mut session = session(&executable);
+
+        let failed = session.render(vec![GnuCommand::new("fail")].into());
+        let rendered = session.render(vec![GnuCommand::new("plot 2")].into());
+
+        assert!(matches!(failed, Err(RenderError::GnuplotError(ref error)) if error.index == Some(0)));
+        assert!(rendered.is_ok());
+        assert_eq!(log(&log_file).iter().filter(|line| *line == "started").count(), 2);
+    }
+
+    #[test]
+    fn test_redirecting_print_does_not_block_synchronization() {
+        let (executable, _) = stub("redirected");
+        let mut session = session(&executable);
+
+        let first = session.render(vec![GnuCommand::new("set print '/dev/null'")].into());
+        let second = session.render(vec![GnuCommand::new("plot 3")].into());
+
+        assert!(first.is_ok());
+        assert!(second.is_ok());
+    }
+}
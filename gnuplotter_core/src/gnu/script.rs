@@ -0,0 +1,334 @@
+//! Exports the commands of a plot as a gnuplot script, so a figure can be tweaked by hand without
+//! going through Rust.
+
+use std::collections::VecDeque;
+use std::fs;
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+use crate::prelude::*;
+
+/// Decides how the data files a plot reads are bundled with its script
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum ScriptData {
+    /// Data files are copied next to the script
+    #[default]
+    Files,
+    /// Data is written into the script itself as datablocks
+    Inline
+}
+
+/// The files written when exporting a script
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScriptBundle {
+    pub script: PathBuf,
+    pub data_files: Vec<PathBuf>
+}
+
+pub type ScriptResult = std::result::Result<ScriptBundle, GnuCommandFactoryError>;
+
+pub struct Script;
+
+impl Script {
+    /// Writes the commands as `NAME.gp` into the directory, along with the data files they read.
+    /// Paths in the script are relative to the directory, so running `gnuplot NAME.gp` from within
+    /// it reproduces the plot, writing its output file into the directory as well.
+    pub fn export(commands: VecDeque<GnuCommand>, directory: &str, name: &str, data: ScriptData) -> ScriptResult {
+        let directory = Path::new(directory);
+        fs::create_dir_all(directory)
+            .or(Err(GnuCommandFactoryError::io_error("Unable to create the script directory")))?;
+
+        // the commands keep the data files they read alive until they are copied
+        let (script, data_files) = Script::bundle(&commands, data)?;
+
+        let mut written = vec![];
+        for (source, target) in data_files {
+            let target = directory.join(target);
+            fs::copy(&source, &target)
+                .or(Err(GnuCommandFactoryError::io_error("Unable to copy a data file into the script directory")))?;
+            written.push(target);
+        }
+
+        let script_file = directory.join(format!("{}.gp", name));
+        fs::write(&script_file, script)
+            .or(Err(GnuCommandFactoryError::io_error("Unable to write the script")))?;
+
+        Ok(ScriptBundle {
+            script: script_file,
+            data_files: written
+        })
+    }
+
+    /// Builds the script, rewriting the output file and the data files the commands are known to
+    /// read to paths relative to the script. Returns the data files that need to be copied along
+    /// with the name they are given next to the script.
+    fn bundle(commands: &VecDeque<GnuCommand>, data: ScriptData) -> std::result::Result<(String, Vec<(PathBuf, String)>), GnuCommandFactoryError> {
+        let mut lines = vec!["# gnuplot script exported by gnuplotter".to_string()];
+        let mut data_files: Vec<(PathBuf, String)> = vec![];
+        // every data file read so far, along with what the script reads it from instead
+        let mut sources: Vec<(PathBuf, String)> = vec![];
+        let mut datablocks = 0;
+
+        for command in commands {
+            let mut text = command.to_string();
+
+            for (range, path) in quoted_strings(&text).into_iter().rev() {
+                let replacement = if command.selects_output() {
+                    format!("'{}'", file_name(&path).replace('\'', "''"))
+                } else if let Some(reference) = command.data().iter().find(|data| data.path == path) {
+                    let source = PathBuf::from(&path);
+                    match sources.iter().find(|(known, _)| *known == source) {
+                        Some((_, replacement)) => replacement.clone(),
+                        None => {
+                            // datablocks only hold text, so binary data files are always copied
+                            let replacement = if data == ScriptData::Inline && !reference.binary {
+                                datablocks += 1;
+                                lines.push(datablock(&source, datablocks)?);
+                                datablock_name(datablocks)
+                            } else {
+                                let target = unique_name(&file_name(&path), &data_files);
+                                let replacement = format!("'{}'", target.replace('\'', "''"));
+                                data_files.push((source.clone(), target));
+                                replacement
+                            };
+                            sources.push((source, replacement.clone()));
+                            replacement
+                        }
+                    }
+                } else {
+                    continue;
+                };
+
+                text.replace_range(range, &replacement);
+            }
+
+            lines.push(text);
+        }
+
+        Ok((lines.join("\n") + "\n", data_files))
+    }
+}
+
+/// Replaces the data files the commands are known to read with datablocks defined ahead of them, so
+/// the commands no longer depend on files that may be overwritten before gnuplot reads them.
+pub(crate) fn inline_data(commands: VecDeque<GnuCommand>) -> GnuCommandFactoryResult {
+    let mut inlined = VecDeque::new();
+    let mut sources: Vec<PathBuf> = vec![];
+
+    for command in commands {
+        // datablocks only hold text, so binary data files are still read from disk
+        if command.data().is_empty() || command.data().iter().any(|data| data.binary) {
+            inlined.push_back(command);
+            continue;
+        }
+
+        let mut text = command.to_string();
+        for (range, path) in quoted_strings(&text).into_iter().rev() {
+            if !command.data().iter().any(|data| data.path == path) {
+                continue;
+            }
+
+            let source = PathBuf::from(&path);
+            let index = match sources.iter().position(|known| *known == source) {
+                Some(index) => index,
+                None => {
+                    let definition = datablock(&source, sources.len() + 1)?;
+                    inlined.push_back(GnuCommand::new(definition).requires(Version::new(5, 0, 0)));
+                    sources.push(source);
+                    sources.len() - 1
+                }
+            };
+            text.replace_range(range, &datablock_name(index + 1));
+        }
+
+        let mut rewritten = GnuCommand::new(text);
+        if let Some(version) = command.required_version() {
+            rewritten = rewritten.requires(version);
+        }
+        inlined.push_back(rewritten);
+    }
+
+    Ok(inlined)
+}
+
+fn datablock_name(number: usize) -> String {
+    format!("$DATA_{}", number)
+}
+
+/// Defines a datablock containing the file, which can be read like the file itself
+fn datablock(source: &Path, number: usize) -> std::result::Result<String, GnuCommandFactoryError> {
+    let content = fs::read_to_string(source)
+        .or(Err(GnuCommandFactoryError::io_error("Unable to read a data file to inline it")))?;
+
+    Ok(format!("{} << EOD\n{}\nEOD", datablock_name(number), content.trim_end()))
+}
+
+fn file_name(path: &str) -> String {
+    Path::new(path)
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_else(|| path.to_string())
+}
+
+/// Prefixes the name of a data file when another data file already goes by the same name
+fn unique_name(name: &str, data_files: &[(PathBuf, String)]) -> String {
+    let taken = |candidate: &str| data_files.iter().any(|(_, target)| target == candidate);
+
+    let mut candidate = name.to_string();
+    let mut number = 1;
+    while taken(&candidate) {
+        number += 1;
+        candidate = format!("{}_{}", number, name);
+    }
+
+    candidate
+}
+
+/// Finds the strings quoted in a command, returning the range of each string including its quotes
+/// along with its unescaped content. Single quoted strings escape a quote by doubling it, while
+/// double quoted strings escape it with a backslash.
+pub(crate) fn quoted_strings(command: &str) -> Vec<(Range<usize>, String)> {
+    let mut strings = vec![];
+    let mut chars = command.char_indices().peekable();
+
+    while let Some((start, quote)) = chars.next() {
+        if quote != '\'' && quote != '"' {
+            continue;
+        }
+
+        let mut content = String::new();
+        while let Some((index, c)) = chars.next() {
+            if c == quote {
+                if quote == '\'' && chars.peek().map(|(_, next)| *next) == Some('\'') {
+                    chars.next();
+                    content.push(c);
+                    continue;
+                }
+                strings.push((start..index + 1, content));
+                break;
+            }
+            if c == '\\' && quote == '"' {
+                if let Some((_, escaped)) = chars.next() {
+                    content.push(escaped);
+                }
+                continue;
+            }
+            content.push(c);
+        }
+    }
+
+    strings
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+    use super::*;
+
+    /// Writes a data file which is removed once dropped at the end of a test
+    fn data_file(name: &str) -> (DataFile, String) {
+        let (data_file, mut file) = DataWorkspace::default().allocate(name).unwrap();
+        file.write_all(b"0\t1\n1\t2\n").unwrap();
+        let path = data_file.path().display().to_string();
+
+        (data_file, path)
+    }
+
+    #[test]
+    fn test_quoted_strings_are_found() {
+        let strings = quoted_strings("plot \"a \\\"b\\\".txt\" title 'it''s'");
+
+        assert_eq!(strings.len(), 2);
+        assert_eq!(strings[0], (5..18, "a \"b\".txt".to_string()));
+        assert_eq!(strings[1].1, "it's");
+    }
+
+    #[test]
+    fn test_paths_are_made_relative_to_the_script() {
+        let (_file, data) = data_file("relative");
+        let name = file_name(&data);
+        let commands: VecDeque<GnuCommand> = vec![
+            GnuCommand::new("set output './.tmp/output.png'"),
+            GnuCommand::new(format!("set title '{}'", data)),
+            GnuCommand::new(format!("plot \"{}\" using 1:2 title 'A', \"{}\" using 1:3", data, data)).reads(&data),
+        ].into();
+
+        let (script, data_files) = Script::bundle(&commands, ScriptData::Files).unwrap();
+
+        assert_eq!(script, format!("# gnuplot script exported by gnuplotter\n\
+            set output 'output.png'\n\
+            set title '{}'\n\
+            plot '{}' using 1:2 title 'A', '{}' using 1:3\n", data, name, name));
+        assert_eq!(data_files, vec![(PathBuf::from(&data), name)]);
+    }
+
+    #[test]
+    fn test_data_files_are_inlined() {
+        let (_file, data) = data_file("inline");
+        let commands: VecDeque<GnuCommand> = vec![
+            GnuCommand::new(format!("plot \"{}\" using 1:2", data)).reads(&data),
+        ].into();
+
+        let (script, data_files) = Script::bundle(&commands, ScriptData::Inline).unwrap();
+
+        assert_eq!(script, "# gnuplot script exported by gnuplotter\n\
+            $DATA_1 << EOD\n0\t1\n1\t2\nEOD\n\
+            plot $DATA_1 using 1:2\n");
+        assert!(data_files.is_empty());
+    }
+
+    #[test]
+    fn test_data_files_are_inlined_into_the_commands() {
+        let (_file, data) = data_file("commands");
+        let commands: VecDeque<GnuCommand> = vec![
+            GnuCommand::new("set output './output.png'"),
+            GnuCommand::new(format!("set title '{}'", data)),
+            GnuCommand::new(format!("plot \"{}\" using 1:2, \"{}\" using 1:3", data, data)).reads(&data),
+        ].into();
+
+        let inlined = inline_data(commands).unwrap();
+
+        assert_eq!(inlined.len(), 4);
+        assert_eq!(inlined[0].to_string(), "set output './output.png'");
+        assert_eq!(inlined[1].to_string(), format!("set title '{}'", data));
+        assert_eq!(inlined[2].to_string(), "$DATA_1 << EOD\n0\t1\n1\t2\nEOD");
+        assert_eq!(inlined[2].required_version(), Some(Version::new(5, 0, 0)));
+        assert_eq!(inlined[3].to_string(), "plot $DATA_1 using 1:2, $DATA_1 using 1:3");
+    }
+
+    #[test]
+    fn test_binary_data_files_are_not_inlined() {
+        let (_file, data) = data_file("binary");
+        let plot = format!("plot \"{}\" binary format=\"%float64%float64\" endian=little using 1:2", data);
+
+        let inlined = inline_data(vec![GnuCommand::new(plot.clone()).reads_binary(&data)].into()).unwrap();
+
+        assert_eq!(inlined, vec![GnuCommand::new(plot)]);
+    }
+
+    #[test]
+    fn test_binary_data_files_are_copied_when_inlining() {
+        let (_text_file, text) = data_file("bundle_text");
+        let (_binary_file, binary) = data_file("bundle_binary");
+        let binary_name = file_name(&binary);
+        let commands: VecDeque<GnuCommand> = vec![
+            GnuCommand::new(format!("plot \"{}\" using 1:2", text)).reads(&text),
+            GnuCommand::new(format!("plot \"{}\" binary format=\"%float64%float64\" endian=little using 1:2", binary)).reads_binary(&binary),
+        ].into();
+
+        let (script, data_files) = Script::bundle(&commands, ScriptData::Inline).unwrap();
+
+        assert_eq!(script, format!("# gnuplot script exported by gnuplotter\n\
+            $DATA_1 << EOD\n0\t1\n1\t2\nEOD\n\
+            plot $DATA_1 using 1:2\n\
+            plot '{}' binary format=\"%float64%float64\" endian=little using 1:2\n", binary_name));
+        assert_eq!(data_files, vec![(PathBuf::from(&binary), binary_name)]);
+    }
+
+    #[test]
+    fn test_data_files_with_the_same_name_are_kept_apart() {
+        let taken = vec![(PathBuf::from("/a/data.txt"), "data.txt".to_string())];
+
+        assert_eq!(unique_name("data.txt", &taken), "2_data.txt");
+        assert_eq!(unique_name("other.txt", &taken), "other.txt");
+    }
+}
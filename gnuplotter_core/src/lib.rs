@@ -17,6 +17,16 @@ pub mod prelude {
     pub use super::gnu::data::source::vector_data_source::*;
     pub use super::gnu::data::serie::*;
     pub use super::gnu::data::series::*;
+    pub use super::gnu::data::workspace::*;
+    pub use super::gnu::data::xy_serie::*;
+    pub use super::gnu::data::xy_series::*;
     pub use super::gnu::render::*;
+    pub use super::gnu::render::backend::*;
+    pub use super::gnu::render::batch::*;
+    pub use super::gnu::render::diagnostic::*;
+    pub use super::gnu::render::executable::*;
+    pub use super::gnu::render::limits::*;
+    pub use super::gnu::render::session::*;
+    pub use super::gnu::script::*;
     pub use super::result::*;
 }
\ No newline at end of file
@@ -1,8 +1,9 @@
 use crate::maybe::Maybe::Nothing;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 pub enum Maybe<T> {
     Value(T),
+    #[default]
     Nothing
 }
 
@@ -12,7 +13,7 @@ impl<T> Maybe<T> {
     }
 
     pub fn update<S>(&mut self, value: S) where S: Into<T> {
-        std::mem::swap(self, &mut Maybe::Value(value.into()));
+        *self = Maybe::Value(value.into());
     }
 
     pub fn unwrap(self) -> T {
@@ -37,12 +38,6 @@ pub mod maybe_conversions {
     }
 }
 
-impl<T> Default for Maybe<T> {
-    fn default() -> Self {
-        Maybe::Nothing
-    }
-}
-
 #[cfg(test)]
 mod tests {
     use crate::prelude::Required;
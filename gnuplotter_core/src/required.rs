@@ -16,7 +16,7 @@ impl<T> Required<T> {
     }
 
     pub fn update<S>(&mut self, value: S) where S: Into<T> {
-        std::mem::swap(self, &mut Required::value(value.into()));
+        *self = Required::value(value.into());
     }
 
     pub fn unwrap(self) -> T {
@@ -8,14 +8,18 @@ pub(crate) fn gnu_command_factory(item: &ItemStruct) -> proc_macro2::TokenStream
     let where_clause = &item.generics.where_clause;
 
     let mut implementation = quote!{};
+    let mut implementation_without_output = quote!{};
     for field in &item.fields {
         let field_name = &field.ident;
         implementation = quote! {
             #implementation
 
-            if let Ok(mut new_commands) = GnuCommandFactory::as_commands(&self.#field_name) {
-                commands.append(&mut new_commands);
-            }
+            commands.append(&mut GnuCommandFactory::as_commands(&self.#field_name)?);
+        };
+        implementation_without_output = quote! {
+            #implementation_without_output
+
+            commands.append(&mut GnuCommandFactory::as_commands_without_output(&self.#field_name)?);
         };
     }
     implementation = quote! {
@@ -24,10 +28,17 @@ pub(crate) fn gnu_command_factory(item: &ItemStruct) -> proc_macro2::TokenStream
                 let mut commands = VecDeque::new();
                 #implementation
 
-                Ok(commands)
+                Ok(GnuCommand::typeset(commands))
+            }
+
+            fn as_commands_without_output(&self) -> GnuCommandFactoryResult {
+                let mut commands = VecDeque::new();
+                #implementation_without_output
+
+                Ok(GnuCommand::typeset(commands))
             }
         }
     };
 
     implementation
-}
\ No newline at end of file
+}